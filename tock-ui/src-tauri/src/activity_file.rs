@@ -0,0 +1,359 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::Activity;
+
+// Reader/writer for the plain text activity log used by the tock CLI.
+//
+// Each line holds one activity:
+//   2026-01-01 13:46 - 2026-01-01 14:49 | tock-ui | write docs
+// A running activity has no end time:
+//   2026-01-01 15:00 | tock-ui | review PRs
+// A literal "|" inside a project or description is escaped as "\|".
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug)]
+pub enum FileError {
+    Io(std::io::Error),
+    // A line did not match the expected format
+    Unrecognized { line_number: usize, line: String },
+}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileError::Io(e) => write!(f, "Failed to access activity file: {}", e),
+            FileError::Unrecognized { line_number, line } => {
+                write!(f, "Unrecognized activity on line {}: {}", line_number, line)
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for FileError {
+    fn from(e: std::io::Error) -> Self {
+        FileError::Io(e)
+    }
+}
+
+pub struct ActivityFile {
+    path: PathBuf,
+}
+
+impl ActivityFile {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    // Resolve the activity file location: an explicit preference wins, then
+    // the TOCK_FILE environment variable, then ~/.tock.txt
+    pub fn locate(preference: Option<&str>) -> Self {
+        let path = preference
+            .filter(|p| !p.trim().is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("TOCK_FILE").map(PathBuf::from))
+            .unwrap_or_else(|| {
+                let mut path = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+                path.push(".tock.txt");
+                path
+            });
+        Self::new(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Read the whole file, keeping lines that are not activities
+    pub fn read_log(&self) -> Result<ActivityLog, FileError> {
+        let content = fs::read_to_string(&self.path)?;
+        ActivityLog::parse(&content)
+    }
+
+    // Read every activity in file order (oldest first)
+    pub fn read_all(&self) -> Result<Vec<Activity>, FileError> {
+        Ok(self.read_log()?.activities().cloned().collect())
+    }
+
+    // Activities that started on a day within [start, end]
+    pub fn read_between(&self, start: NaiveDate, end: NaiveDate) -> Result<Vec<Activity>, FileError> {
+        Ok(self
+            .read_all()?
+            .into_iter()
+            .filter(|a| {
+                a.start_time
+                    .map(|s| s.date() >= start && s.date() <= end)
+                    .unwrap_or(false)
+            })
+            .collect())
+    }
+
+    // Replace the whole file. Writes go to a temporary file first so a crash
+    // never leaves a half-written activity log behind.
+    pub fn write_log(&self, log: &ActivityLog) -> Result<(), FileError> {
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut file = fs::File::create(&tmp_path)?;
+            file.write_all(log.to_string().as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+// Every line of the activity file. Comments, blank lines and activities that
+// were not changed are written back exactly as they were read.
+#[derive(Debug, Clone)]
+pub struct ActivityLog {
+    lines: Vec<Line>,
+    // "\r\n" when the file uses Windows line endings
+    newline: &'static str,
+}
+
+#[derive(Debug, Clone)]
+enum Line {
+    // `raw` is the line as read, None for activities added since
+    Activity { activity: Activity, raw: Option<String> },
+    Other(String),
+}
+
+impl ActivityLog {
+    pub fn parse(content: &str) -> Result<Self, FileError> {
+        let mut lines = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                lines.push(Line::Other(line.to_string()));
+                continue;
+            }
+
+            match parse_line(trimmed) {
+                Some(activity) => lines.push(Line::Activity {
+                    activity,
+                    raw: Some(line.to_string()),
+                }),
+                None => {
+                    return Err(FileError::Unrecognized {
+                        line_number: index + 1,
+                        line: trimmed.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(ActivityLog {
+            lines,
+            newline: if content.contains("\r\n") { "\r\n" } else { "\n" },
+        })
+    }
+
+    // Activities in file order
+    pub fn activities(&self) -> impl Iterator<Item = &Activity> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Activity { activity, .. } => Some(activity),
+            Line::Other(_) => None,
+        })
+    }
+
+    pub fn activities_mut(&mut self) -> impl Iterator<Item = &mut Activity> {
+        self.lines.iter_mut().filter_map(|line| match line {
+            Line::Activity { activity, .. } => Some(activity),
+            Line::Other(_) => None,
+        })
+    }
+
+    // Append an activity at the end of the file
+    pub fn push(&mut self, activity: Activity) {
+        self.lines.push(Line::Activity { activity, raw: None });
+    }
+
+    // Add an activity right after the last one that started before it, so
+    // the file stays in order without moving any other line
+    pub fn insert(&mut self, activity: Activity) {
+        let position = self
+            .lines
+            .iter()
+            .rposition(|line| match line {
+                Line::Activity { activity: a, .. } => a.start_time <= activity.start_time,
+                Line::Other(_) => false,
+            })
+            .map(|i| i + 1);
+        let position = match position {
+            Some(i) => i,
+            // Before the first activity, after any comments heading the file
+            None => self
+                .lines
+                .iter()
+                .position(|line| matches!(line, Line::Activity { .. }))
+                .unwrap_or(self.lines.len()),
+        };
+        self.lines.insert(position, Line::Activity { activity, raw: None });
+    }
+}
+
+impl std::fmt::Display for ActivityLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let newline = self.newline;
+        for line in &self.lines {
+            match line {
+                Line::Activity { activity, raw: Some(raw) }
+                    if parse_line(raw.trim()).as_ref() == Some(activity) =>
+                {
+                    write!(f, "{}{}", raw, newline)?
+                }
+                Line::Activity { activity, .. } => {
+                    if let Some(line) = format_line(activity) {
+                        write!(f, "{}{}", line, newline)?
+                    }
+                }
+                Line::Other(raw) => write!(f, "{}{}", raw, newline)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parse one line of the activity file
+pub fn parse_line(line: &str) -> Option<Activity> {
+    let fields = split_fields(line);
+    if fields.len() != 3 {
+        return None;
+    }
+
+    let times = fields[0].trim();
+    let (start, end) = match times.split_once(" - ") {
+        Some((start, end)) => (parse_datetime(start)?, Some(parse_datetime(end)?)),
+        None => (parse_datetime(times)?, None),
+    };
+
+    Some(Activity {
+        project: fields[1].trim().to_string(),
+        description: fields[2].trim().to_string(),
+        start_time: Some(start),
+        end_time: end,
+        duration_seconds: end.map(|e| (e - start).num_seconds()),
+    })
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(value.trim(), DATETIME_FORMAT).ok()
+}
+
+// Split on unescaped "|" and unescape the resulting fields
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                current.push('|');
+                chars.next();
+            }
+            '|' => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+fn format_line(activity: &Activity) -> Option<String> {
+    let start = activity.start_time?;
+    let times = match activity.end_time {
+        Some(end) => format!("{} - {}", start.format(DATETIME_FORMAT), end.format(DATETIME_FORMAT)),
+        None => start.format(DATETIME_FORMAT).to_string(),
+    };
+
+    Some(format!(
+        "{} | {} | {}",
+        times,
+        escape(&activity.project),
+        escape(&activity.description)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, DATETIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn parses_and_formats_lines() {
+        let line = r"2026-01-01 13:46 - 2026-01-01 14:49 | a\|b | write \| docs";
+        let activity = parse_line(line).unwrap();
+        assert_eq!(activity.project, "a|b");
+        assert_eq!(activity.description, "write | docs");
+        assert_eq!(activity.start_time, Some(at("2026-01-01 13:46")));
+        assert_eq!(activity.end_time, Some(at("2026-01-01 14:49")));
+        assert_eq!(activity.duration_seconds, Some(63 * 60));
+        assert_eq!(format_line(&activity).as_deref(), Some(line));
+
+        let running = parse_line("2026-01-01 15:00 | tock-ui | review PRs").unwrap();
+        assert_eq!(running.end_time, None);
+        assert_eq!(running.duration_seconds, None);
+        assert_eq!(format_line(&running).as_deref(), Some("2026-01-01 15:00 | tock-ui | review PRs"));
+
+        // A backslash not followed by "|" is kept
+        assert_eq!(split_fields(r"a\b | c"), [r"a\b ", " c"]);
+        assert!(parse_line("2026-01-01 15:00 | only project").is_none());
+        assert!(parse_line("2026-01-01 15:00 | a | b | c").is_none());
+        assert!(parse_line("yesterday | tock-ui | review PRs").is_none());
+    }
+
+    #[test]
+    fn reports_unrecognized_line_number() {
+        match ActivityLog::parse("# log\n2026-01-01 15:00 | a | b\nnot an activity\n") {
+            Err(FileError::Unrecognized { line_number, line }) => {
+                assert_eq!(line_number, 3);
+                assert_eq!(line, "not an activity");
+            }
+            other => panic!("expected an unrecognized line, got {:?}", other.map(|log| log.to_string())),
+        }
+    }
+
+    #[test]
+    fn writes_back_lines_it_does_not_change() {
+        let content = "# work log\n\n2026-01-01 09:00 - 2026-01-01 10:00 |  old  | spacing kept\n  # indented comment\n2026-01-01 11:00 | tock-ui | running\n";
+        let mut log = ActivityLog::parse(content).unwrap();
+        assert_eq!(log.to_string(), content);
+        assert_eq!(log.activities().count(), 2);
+
+        for activity in log.activities_mut().filter(|a| a.end_time.is_none()) {
+            activity.end_time = Some(at("2026-01-01 12:00"));
+        }
+        log.insert(Activity {
+            project: "p".to_string(),
+            description: "between".to_string(),
+            start_time: Some(at("2026-01-01 10:30")),
+            end_time: Some(at("2026-01-01 10:45")),
+            duration_seconds: Some(15 * 60),
+        });
+        assert_eq!(
+            log.to_string(),
+            "# work log\n\n2026-01-01 09:00 - 2026-01-01 10:00 |  old  | spacing kept\n2026-01-01 10:30 - 2026-01-01 10:45 | p | between\n  # indented comment\n2026-01-01 11:00 - 2026-01-01 12:00 | tock-ui | running\n"
+        );
+    }
+
+    #[test]
+    fn inserts_earliest_activity_after_leading_comments() {
+        let mut log = ActivityLog::parse("# header\r\n2026-01-02 09:00 | a | b\r\n").unwrap();
+        log.insert(parse_line("2026-01-01 09:00 - 2026-01-01 10:00 | c | d").unwrap());
+        assert_eq!(
+            log.to_string(),
+            "# header\r\n2026-01-01 09:00 - 2026-01-01 10:00 | c | d\r\n2026-01-02 09:00 | a | b\r\n"
+        );
+    }
+}
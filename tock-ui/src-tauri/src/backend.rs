@@ -0,0 +1,822 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use std::future::Future;
+use std::pin::Pin;
+use tokio::task::JoinSet;
+
+use crate::activity_file::{ActivityFile, ActivityLog};
+use crate::capabilities::TockCapabilities;
+use crate::process::Limits;
+use crate::report::format_hours_minutes;
+use crate::tock_binary::TockBinary;
+use crate::{
+    execute_tock_command, execute_tock_command_cached, execute_tock_command_cached_async, get_capabilities, parser,
+    Activity, CommandResult,
+};
+
+// How many `tock report` processes a multi-day report runs at once. Each one
+// reads the whole activity file, so more mostly adds contention.
+const MAX_PARALLEL_REPORTS: usize = 4;
+
+pub type DailyReports = Vec<(NaiveDate, CommandResult)>;
+
+pub type DailyReportsFuture<'a> = Pin<Box<dyn Future<Output = DailyReports> + Send + 'a>>;
+
+// Called with each day's report as soon as it is ready, in no particular order
+pub type OnReport<'a> = &'a (dyn Fn(NaiveDate, &CommandResult) + Send + Sync);
+
+// Which day(s) a report should cover
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportDate {
+    All,
+    Today,
+    Yesterday,
+    Date(String), // Format: "YYYY-MM-DD"
+}
+
+impl ReportDate {
+    // Map the `date_type` / `date` pair sent by the frontend
+    pub fn from_request(date_type: &str, date: Option<&str>) -> Self {
+        match (date_type, date) {
+            ("today", _) => ReportDate::Today,
+            ("yesterday", _) => ReportDate::Yesterday,
+            ("date", Some(d)) => ReportDate::Date(d.to_string()),
+            _ => ReportDate::All,
+        }
+    }
+}
+
+// Operations every time tracking backend must support. Read operations
+// return output in the same text format as the tock CLI so that existing
+// callers and parsers keep working regardless of the backend in use.
+pub trait TimeTrackerBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn start(&self, project: &str, description: &str, time: Option<&str>) -> CommandResult;
+
+    fn stop(&self, time: Option<&str>) -> CommandResult;
+
+    fn add(
+        &self,
+        project: &str,
+        description: &str,
+        start: &str,
+        end: Option<&str>,
+        duration: Option<&str>,
+    ) -> CommandResult;
+
+    fn continue_activity(
+        &self,
+        index: Option<u32>,
+        description: Option<&str>,
+        project: Option<&str>,
+        time: Option<&str>,
+    ) -> CommandResult;
+
+    fn current(&self) -> CommandResult;
+
+    fn last(&self, number: Option<u32>) -> CommandResult;
+
+    fn report(&self, date: &ReportDate) -> CommandResult;
+
+    // Why reports for past days can't be produced at all, e.g. because the
+    // installed tock is too old. Checked before a multi-day report.
+    fn daily_reports_unavailable(&self) -> Option<String> {
+        None
+    }
+
+    // Reports for every day in [start, end], in date order. The default runs
+    // one report per day; backends that can answer in a single pass or in
+    // parallel should override it.
+    fn daily_reports<'a>(&'a self, start: NaiveDate, end: NaiveDate, on_report: OnReport<'a>) -> DailyReportsFuture<'a> {
+        Box::pin(async move {
+            let mut reports = Vec::new();
+            let mut day = start;
+
+            while day <= end {
+                let result = self.report(&ReportDate::Date(day.format("%Y-%m-%d").to_string()));
+                let interrupted = result.interrupted.is_some();
+                on_report(day, &result);
+                reports.push((day, result));
+
+                // The remaining days would be stopped the same way
+                if interrupted {
+                    break;
+                }
+
+                // Move to next day, break if we can't (edge case at max date)
+                day = match day.succ_opt() {
+                    Some(d) => d,
+                    None => {
+                        println!("Warning: Could not increment date beyond {}. This may indicate we've reached the maximum date value.", day);
+                        break;
+                    }
+                };
+            }
+
+            reports
+        })
+    }
+}
+
+// Backend that shells out to the `tock` binary
+pub struct CliBackend {
+    tock: TockBinary,
+    limits: Limits,
+}
+
+impl CliBackend {
+    pub fn new(tock: TockBinary, limits: Limits) -> Self {
+        Self { tock, limits }
+    }
+
+    // Whether the installed tock has a feature. Assumed when detection
+    // fails, leaving tock to report the problem itself.
+    fn supports(&self, feature: impl Fn(&TockCapabilities) -> bool) -> bool {
+        match get_capabilities().get(&self.tock) {
+            Ok(capabilities) => feature(&capabilities),
+            Err(_) => true,
+        }
+    }
+}
+
+// Error for a feature the installed tock lacks
+fn unsupported(feature: &str) -> String {
+    format!("The installed tock does not support {}. Please update tock.", feature)
+}
+
+impl TimeTrackerBackend for CliBackend {
+    fn name(&self) -> &'static str {
+        "cli"
+    }
+
+    fn start(&self, project: &str, description: &str, time: Option<&str>) -> CommandResult {
+        let mut args = vec!["start", "-p", project, "-d", description];
+
+        if let Some(t) = time {
+            args.push("-t");
+            args.push(t);
+        }
+
+        execute_tock_command(&self.tock, args, &self.limits)
+    }
+
+    fn stop(&self, time: Option<&str>) -> CommandResult {
+        let mut args = vec!["stop"];
+
+        if let Some(t) = time {
+            args.push("-t");
+            args.push(t);
+        }
+
+        execute_tock_command(&self.tock, args, &self.limits)
+    }
+
+    fn add(
+        &self,
+        project: &str,
+        description: &str,
+        start: &str,
+        end: Option<&str>,
+        duration: Option<&str>,
+    ) -> CommandResult {
+        let mut args = vec!["add", "-p", project, "-d", description, "-s", start];
+
+        let computed_end: String;
+
+        if let Some(e) = end {
+            args.push("-e");
+            args.push(e);
+        } else if let Some(dur) = duration {
+            if self.supports(|c| c.add_duration) {
+                args.push("--duration");
+                args.push(dur);
+            } else {
+                // Older tock only takes an end time, so work it out here
+                computed_end = match end_after(start, dur) {
+                    Some(e) => e,
+                    None => return error_result(format!("Invalid start time or duration: {} / {}", start, dur)),
+                };
+                args.push("-e");
+                args.push(&computed_end);
+            }
+        }
+
+        execute_tock_command(&self.tock, args, &self.limits)
+    }
+
+    fn continue_activity(
+        &self,
+        index: Option<u32>,
+        description: Option<&str>,
+        project: Option<&str>,
+        time: Option<&str>,
+    ) -> CommandResult {
+        if !self.supports(|c| c.continue_activity) {
+            return error_result(unsupported("`tock continue`"));
+        }
+
+        let mut args = vec!["continue"];
+        let idx_str: String;
+
+        if let Some(idx) = index {
+            idx_str = idx.to_string();
+            args.push(&idx_str);
+        }
+
+        if let Some(d) = description {
+            args.push("-d");
+            args.push(d);
+        }
+
+        if let Some(p) = project {
+            args.push("-p");
+            args.push(p);
+        }
+
+        if let Some(t) = time {
+            args.push("-t");
+            args.push(t);
+        }
+
+        execute_tock_command(&self.tock, args, &self.limits)
+    }
+
+    fn current(&self) -> CommandResult {
+        execute_tock_command_cached(&self.tock, vec!["current"], true, &self.limits)
+    }
+
+    fn last(&self, number: Option<u32>) -> CommandResult {
+        let mut args = vec!["last"];
+        let n_str: String;
+
+        if let Some(n) = number {
+            n_str = n.to_string();
+            args.push("-n");
+            args.push(&n_str);
+        }
+
+        execute_tock_command_cached(&self.tock, args, true, &self.limits)
+    }
+
+    fn report(&self, date: &ReportDate) -> CommandResult {
+        let mut args = vec!["report"];
+
+        match date {
+            ReportDate::All => {}
+            ReportDate::Today => args.push("--today"),
+            ReportDate::Yesterday => args.push("--yesterday"),
+            ReportDate::Date(d) => {
+                if !self.supports(|c| c.report_date) {
+                    return error_result(unsupported("reports for a given day (`tock report --date`)"));
+                }
+                args.push("--date");
+                args.push(d);
+            }
+        }
+
+        execute_tock_command_cached(&self.tock, args, true, &self.limits)
+    }
+
+    fn daily_reports_unavailable(&self) -> Option<String> {
+        if self.supports(|c| c.report_date) {
+            None
+        } else {
+            Some(unsupported("reports for a given day (`tock report --date`)"))
+        }
+    }
+
+    // One `tock report --date` per day, up to MAX_PARALLEL_REPORTS at a time
+    fn daily_reports<'a>(&'a self, start: NaiveDate, end: NaiveDate, on_report: OnReport<'a>) -> DailyReportsFuture<'a> {
+        Box::pin(async move {
+            let mut pending = start.iter_days().take_while(|day| *day <= end);
+            let mut running = JoinSet::new();
+            let mut reports = Vec::new();
+
+            loop {
+                while running.len() < MAX_PARALLEL_REPORTS {
+                    let day = match pending.next() {
+                        Some(day) => day,
+                        None => break,
+                    };
+                    let args = vec!["report".to_string(), "--date".to_string(), day.format("%Y-%m-%d").to_string()];
+                    let (tock, limits) = (self.tock.clone(), self.limits.clone());
+                    running.spawn(async move { (day, execute_tock_command_cached_async(&tock, args, &limits).await) });
+                }
+
+                let (day, result) = match running.join_next().await {
+                    Some(Ok(report)) => report,
+                    Some(Err(e)) => {
+                        eprintln!("Warning: Report task failed: {}", e);
+                        continue;
+                    }
+                    None => break,
+                };
+                let interrupted = result.interrupted.is_some();
+                on_report(day, &result);
+                reports.push((day, result));
+
+                // The other days would be stopped the same way. Dropping the
+                // set aborts them, which kills their processes.
+                if interrupted {
+                    break;
+                }
+            }
+
+            reports.sort_by_key(|(day, _)| *day);
+            reports
+        })
+    }
+}
+
+// Backend that reads and writes tock's activity file directly. Any operation
+// falls back to the CLI when the file is missing or its format is not
+// recognized.
+pub struct NativeBackend {
+    file: ActivityFile,
+    cli: CliBackend,
+}
+
+impl NativeBackend {
+    pub fn new(file: ActivityFile, cli: CliBackend) -> Self {
+        Self { file, cli }
+    }
+
+    fn load(&self) -> Option<ActivityLog> {
+        match self.file.read_log() {
+            Ok(log) => Some(log),
+            Err(e) => {
+                eprintln!("Warning: {} ({}). Falling back to tock CLI.", e, self.file.path().display());
+                None
+            }
+        }
+    }
+
+    fn save(&self, log: &ActivityLog, message: String) -> CommandResult {
+        match self.file.write_log(log) {
+            Ok(_) => CommandResult {
+                success: true,
+                output: message,
+                error: None,
+                interrupted: None,
+            },
+            Err(e) => error_result(e.to_string()),
+        }
+    }
+}
+
+impl TimeTrackerBackend for NativeBackend {
+    fn name(&self) -> &'static str {
+        "native"
+    }
+
+    fn start(&self, project: &str, description: &str, time: Option<&str>) -> CommandResult {
+        let mut log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.start(project, description, time),
+        };
+
+        let start = match resolve_time(time, now().date()) {
+            Some(t) => t,
+            None => return error_result(format!("Invalid time: {}", time.unwrap_or_default())),
+        };
+
+        stop_running(log.activities_mut(), start);
+        log.push(new_activity(project, description, start, None));
+        self.save(&log, format!("Started {} - {}", project, description))
+    }
+
+    fn stop(&self, time: Option<&str>) -> CommandResult {
+        let mut log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.stop(time),
+        };
+
+        let end = match resolve_time(time, now().date()) {
+            Some(t) => t,
+            None => return error_result(format!("Invalid time: {}", time.unwrap_or_default())),
+        };
+
+        if !stop_running(log.activities_mut(), end) {
+            return error_result("No activity is currently running".to_string());
+        }
+        self.save(&log, "Activity stopped".to_string())
+    }
+
+    fn add(
+        &self,
+        project: &str,
+        description: &str,
+        start: &str,
+        end: Option<&str>,
+        duration: Option<&str>,
+    ) -> CommandResult {
+        let mut log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.add(project, description, start, end, duration),
+        };
+
+        let start_time = match resolve_time(Some(start), now().date()) {
+            Some(t) => t,
+            None => return error_result(format!("Invalid start time: {}", start)),
+        };
+
+        let end_time = if let Some(e) = end {
+            resolve_time(Some(e), start_time.date())
+        } else {
+            duration
+                .and_then(parser::parse_duration)
+                .map(|d| start_time + d)
+        };
+
+        let end_time = match end_time {
+            Some(t) if t >= start_time => t,
+            Some(_) => return error_result("End time must be after start time".to_string()),
+            None => return error_result("A valid end time or duration is required".to_string()),
+        };
+
+        log.insert(new_activity(project, description, start_time, Some(end_time)));
+        self.save(&log, format!("Added {} - {}", project, description))
+    }
+
+    fn continue_activity(
+        &self,
+        index: Option<u32>,
+        description: Option<&str>,
+        project: Option<&str>,
+        time: Option<&str>,
+    ) -> CommandResult {
+        let mut log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.continue_activity(index, description, project, time),
+        };
+
+        let base = match recent(log.activities()).get(index.unwrap_or(0) as usize) {
+            Some(a) => (*a).clone(),
+            None => return error_result("No activity found to continue".to_string()),
+        };
+
+        let start = match resolve_time(time, now().date()) {
+            Some(t) => t,
+            None => return error_result(format!("Invalid time: {}", time.unwrap_or_default())),
+        };
+
+        let project = project.unwrap_or(&base.project).to_string();
+        let description = description.unwrap_or(&base.description).to_string();
+
+        stop_running(log.activities_mut(), start);
+        log.push(new_activity(&project, &description, start, None));
+        self.save(&log, format!("Continued {} - {}", project, description))
+    }
+
+    fn current(&self) -> CommandResult {
+        let log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.current(),
+        };
+
+        let running: Vec<&Activity> = log.activities().filter(|a| a.end_time.is_none()).collect();
+        if running.is_empty() {
+            return CommandResult {
+                success: true,
+                output: "No current activity\n".to_string(),
+                error: None,
+                interrupted: None,
+            };
+        }
+
+        ok_result(format_table(&running))
+    }
+
+    fn last(&self, number: Option<u32>) -> CommandResult {
+        let log = match self.load() {
+            Some(log) => log,
+            None => return self.cli.last(number),
+        };
+
+        let mut recent = recent(log.activities());
+        recent.truncate(number.unwrap_or(10) as usize);
+        ok_result(format_table(&recent))
+    }
+
+    fn report(&self, date: &ReportDate) -> CommandResult {
+        let today = now().date();
+        let day = match date {
+            ReportDate::Today => today,
+            ReportDate::Yesterday => today - Duration::days(1),
+            ReportDate::Date(d) => match NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+                Ok(day) => day,
+                Err(_) => return self.cli.report(date),
+            },
+            ReportDate::All => return self.cli.report(date),
+        };
+
+        match self.file.read_between(day, day) {
+            Ok(activities) => ok_result(format_report(day, &activities)),
+            Err(e) => {
+                eprintln!("Warning: {}. Falling back to tock CLI.", e);
+                self.cli.report(date)
+            }
+        }
+    }
+
+    fn daily_reports<'a>(&'a self, start: NaiveDate, end: NaiveDate, on_report: OnReport<'a>) -> DailyReportsFuture<'a> {
+        let activities = match self.file.read_between(start, end) {
+            Ok(a) => a,
+            Err(e) => {
+                eprintln!("Warning: {}. Falling back to tock CLI.", e);
+                return self.cli.daily_reports(start, end, on_report);
+            }
+        };
+
+        let reports = start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .map(|day| {
+                let for_day: Vec<Activity> = activities
+                    .iter()
+                    .filter(|a| a.start_time.map(|s| s.date()) == Some(day))
+                    .cloned()
+                    .collect();
+                let result = ok_result(format_report(day, &for_day));
+                on_report(day, &result);
+                (day, result)
+            })
+            .collect();
+        Box::pin(async move { reports })
+    }
+}
+
+fn now() -> NaiveDateTime {
+    chrono::Local::now().naive_local()
+}
+
+fn ok_result(output: String) -> CommandResult {
+    CommandResult {
+        success: true,
+        output,
+        error: None,
+        interrupted: None,
+    }
+}
+
+fn error_result(error: String) -> CommandResult {
+    CommandResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+        interrupted: None,
+    }
+}
+
+fn new_activity(project: &str, description: &str, start: NaiveDateTime, end: Option<NaiveDateTime>) -> Activity {
+    Activity {
+        project: project.to_string(),
+        description: description.to_string(),
+        start_time: Some(start),
+        end_time: end,
+        duration_seconds: end.map(|e| (e - start).num_seconds()),
+    }
+}
+
+// Accepts "HH:MM" (on `date`) or "YYYY-MM-DD HH:MM". No value means now.
+pub fn resolve_time(value: Option<&str>, date: NaiveDate) -> Option<NaiveDateTime> {
+    let value = match value.map(str::trim) {
+        Some(v) if !v.is_empty() => v,
+        _ => return Some(now()),
+    };
+
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M")
+        .ok()
+        .or_else(|| NaiveTime::parse_from_str(value, "%H:%M").ok().map(|t| date.and_time(t)))
+}
+
+// End time for `tock add -e`: `start` plus `duration`. Given as HH:MM like
+// the start, unless the start has a date or the end falls on the next day.
+fn end_after(start: &str, duration: &str) -> Option<String> {
+    let start_time = resolve_time(Some(start), now().date())?;
+    let end = start_time + parser::parse_duration(duration)?;
+    if end.date() == start_time.date() && NaiveTime::parse_from_str(start.trim(), "%H:%M").is_ok() {
+        Some(end.format("%H:%M").to_string())
+    } else {
+        Some(end.format("%Y-%m-%d %H:%M").to_string())
+    }
+}
+
+// End every running activity at `end`. Returns whether anything was running.
+fn stop_running<'a>(activities: impl Iterator<Item = &'a mut Activity>, end: NaiveDateTime) -> bool {
+    let mut stopped = false;
+    for activity in activities.filter(|a| a.end_time.is_none()) {
+        let end = activity.start_time.map(|s| s.max(end)).unwrap_or(end);
+        activity.end_time = Some(end);
+        activity.duration_seconds = activity.start_time.map(|s| (end - s).num_seconds());
+        stopped = true;
+    }
+    stopped
+}
+
+// Activities ordered from most recent to oldest
+fn recent<'a>(activities: impl Iterator<Item = &'a Activity>) -> Vec<&'a Activity> {
+    let mut recent: Vec<&Activity> = activities.collect();
+    recent.sort_by_key(|a| std::cmp::Reverse(a.start_time));
+    recent
+}
+
+fn elapsed_seconds(activity: &Activity) -> i64 {
+    match (activity.start_time, activity.end_time) {
+        (Some(s), Some(e)) => (e - s).num_seconds(),
+        (Some(s), None) => (now() - s).num_seconds().max(0),
+        _ => 0,
+    }
+}
+
+// Duration in Go's format, as printed by `tock last`: "45s", "3m0s", "1h3m0s"
+fn format_go_duration(total_seconds: i64) -> String {
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+
+    if hours > 0 {
+        format!("{}h{}m{}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m{}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+// Table in the layout of `tock last` / `tock current`
+fn format_table(activities: &[&Activity]) -> String {
+    let rows: Vec<[String; 4]> = activities
+        .iter()
+        .map(|a| {
+            [
+                a.start_time.map(|s| s.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default(),
+                a.description.clone(),
+                a.project.clone(),
+                format_go_duration(elapsed_seconds(a)),
+            ]
+        })
+        .collect();
+
+    let header = ["Start", "Description", "Project", "Duration"].map(String::from);
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| format!("{:<width$}", cell, width = widths[i]))
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+// Report in the layout of `tock report`, grouped by project. Days without
+// activities produce an empty report.
+fn format_report(day: NaiveDate, activities: &[Activity]) -> String {
+    if activities.is_empty() {
+        return String::new();
+    }
+
+    let mut projects: Vec<(&str, Vec<&Activity>)> = Vec::new();
+    for activity in activities {
+        match projects.iter_mut().find(|(name, _)| *name == activity.project) {
+            Some((_, list)) => list.push(activity),
+            None => projects.push((&activity.project, vec![activity])),
+        }
+    }
+
+    let title = format!("📊 Time Tracking Report: {}", day.format("%Y-%m-%d"));
+    let mut output = format!("{}\n{}\n", title, "=".repeat(40));
+
+    for (project, list) in projects {
+        let total: i64 = list.iter().map(|a| elapsed_seconds(a)).sum();
+        output.push_str(&format!("📁 {}: {}\n", project, format_hours_minutes(total)));
+
+        for activity in list {
+            let start = activity.start_time.map(|s| s.format("%H:%M").to_string()).unwrap_or_default();
+            let end = activity
+                .end_time
+                .map(|e| e.format("%H:%M").to_string())
+                .unwrap_or_else(|| "now".to_string());
+            output.push_str(&format!(
+                "   {} - {} ({}) | {}\n",
+                start,
+                end,
+                format_hours_minutes(elapsed_seconds(activity)),
+                activity.description
+            ));
+        }
+    }
+
+    output
+}
+
+// Resolve the backend for a stored preference value. Unknown or missing
+// values fall back to the CLI backend.
+pub fn backend_for(preference: Option<&str>, file: ActivityFile, cli: CliBackend) -> Box<dyn TimeTrackerBackend> {
+    match preference {
+        Some("native") => Box::new(NativeBackend::new(file, cli)),
+        Some("cli") | None => Box::new(cli),
+        Some(other) => {
+            eprintln!("Warning: Unknown tracker backend '{}', falling back to cli", other);
+            Box::new(cli)
+        }
+    }
+}
+
+// Names of the backends that can be selected
+pub fn available_backends() -> Vec<&'static str> {
+    vec!["cli", "native"]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use std::fs;
+    use std::path::PathBuf;
+
+    // Native backend on an activity file with `content`, in an empty folder
+    // removed by the test when done
+    fn native(name: &str, content: &str) -> (NativeBackend, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("tock-ui-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(".tock.txt");
+        fs::write(&path, content).unwrap();
+
+        let cli = CliBackend::new(TockBinary::from_settings(&Settings::default()), Limits::timeout(crate::process::CHECK_TIMEOUT));
+        (NativeBackend::new(ActivityFile::new(path.clone()), cli), dir)
+    }
+
+    fn read(dir: &std::path::Path) -> String {
+        fs::read_to_string(dir.join(".tock.txt")).unwrap()
+    }
+
+    #[test]
+    fn native_start_and_stop_keep_comments() {
+        let (backend, dir) = native(
+            "native-start",
+            "# my log\n2026-01-02 08:00 | mail | inbox\n\n",
+        );
+
+        let result = backend.start("tock-ui", "docs", Some("2026-01-02 09:00"));
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            read(&dir),
+            "# my log\n2026-01-02 08:00 - 2026-01-02 09:00 | mail | inbox\n\n2026-01-02 09:00 | tock-ui | docs\n"
+        );
+
+        assert!(backend.stop(Some("2026-01-02 10:30")).success);
+        assert!(read(&dir).ends_with("\n2026-01-02 09:00 - 2026-01-02 10:30 | tock-ui | docs\n"));
+        assert!(read(&dir).starts_with("# my log\n"));
+        // Nothing left running
+        let result = backend.stop(Some("2026-01-02 11:00"));
+        assert!(!result.success);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn native_add_and_continue() {
+        let (backend, dir) = native(
+            "native-add",
+            "# my log\n2026-01-01 09:00 - 2026-01-01 10:00 | a | first\n2026-01-01 14:00 - 2026-01-01 15:00 | b | second\n",
+        );
+
+        let result = backend.add("c", "between", "2026-01-01 11:00", None, Some("1h30m"));
+        assert!(result.success, "{:?}", result.error);
+        assert!(!backend.add("c", "backwards", "2026-01-01 11:00", Some("10:00"), None).success);
+
+        // Continues the most recent activity, not the last one added
+        let result = backend.continue_activity(None, None, None, Some("2026-01-01 16:00"));
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            read(&dir),
+            "# my log\n\
+             2026-01-01 09:00 - 2026-01-01 10:00 | a | first\n\
+             2026-01-01 11:00 - 2026-01-01 12:30 | c | between\n\
+             2026-01-01 14:00 - 2026-01-01 15:00 | b | second\n\
+             2026-01-01 16:00 | b | second\n"
+        );
+
+        // Continuing stops the running activity first
+        let result = backend.continue_activity(Some(2), Some("other"), None, Some("2026-01-01 17:00"));
+        assert!(result.success, "{:?}", result.error);
+        assert!(read(&dir).ends_with(
+            "2026-01-01 16:00 - 2026-01-01 17:00 | b | second\n2026-01-01 17:00 | c | other\n"
+        ));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use rusqlite::{Connection, DatabaseName, OpenFlags, Result as SqlResult};
+use serde::Serialize;
+use specta::Type;
+use std::path::{Path, PathBuf};
+
+use crate::error::{AppError, AppResult};
+
+// Rolling snapshots of tock_ui.db, taken with SQLite's online backup API so
+// they are consistent while the app is running. Snapshots live in a
+// `backups/` folder next to the database as
+//   tock_ui_20261017-090000_scheduled.db
+// where the suffix records why the snapshot was taken. Only the newest
+// RETENTION snapshots are kept.
+
+pub const BACKUP_DIR: &str = "backups";
+pub const RETENTION: usize = 10;
+
+const PREFIX: &str = "tock_ui_";
+const EXTENSION: &str = "db";
+
+#[derive(Debug, Serialize, Type)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .map(|parent| parent.join(BACKUP_DIR))
+        .unwrap_or_else(|| PathBuf::from(BACKUP_DIR))
+}
+
+// Snapshot the open database and prune old snapshots. Returns the new file.
+pub fn create(conn: &Connection, db_path: &Path, reason: &str) -> AppResult<PathBuf> {
+    let path = snapshot(conn, db_path, reason)?;
+    prune(&backup_dir(db_path), RETENTION);
+    Ok(path)
+}
+
+// Snapshot the open database without pruning. Used before a restore, which
+// may be restoring the very snapshot the next prune would remove.
+pub fn snapshot(conn: &Connection, db_path: &Path, reason: &str) -> AppResult<PathBuf> {
+    let dir = backup_dir(db_path);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::Io(format!("Failed to create backup folder {}: {}", dir.display(), e)))?;
+
+    let file_name = format!(
+        "{}{}_{}.{}",
+        PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        reason,
+        EXTENSION
+    );
+    let path = dir.join(file_name);
+
+    conn.backup(DatabaseName::Main, &path, None)?;
+    Ok(path)
+}
+
+// Remove all but the newest RETENTION snapshots of the database at `db_path`
+pub fn prune_old(db_path: &Path) {
+    prune(&backup_dir(db_path), RETENTION);
+}
+
+// Snapshots of the database at `db_path`, newest first
+pub fn list(db_path: &Path) -> Vec<BackupInfo> {
+    let dir = backup_dir(db_path);
+    let mut backups: Vec<BackupInfo> = backup_files(&dir)
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(&path).ok()?;
+            let created_at = metadata
+                .modified()
+                .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339())
+                .unwrap_or_default();
+            Some(BackupInfo {
+                file_name: path.file_name()?.to_string_lossy().to_string(),
+                path: path.to_string_lossy().to_string(),
+                created_at,
+                size_bytes: metadata.len(),
+            })
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    backups
+}
+
+// Resolve a snapshot by file name. Only files inside the backup folder can
+// be restored.
+pub fn resolve(db_path: &Path, file_name: &str) -> AppResult<PathBuf> {
+    let dir = backup_dir(db_path);
+    backup_files(&dir)
+        .into_iter()
+        .find(|path| path.file_name().is_some_and(|name| name == file_name))
+        .ok_or_else(|| AppError::NotFound(format!("Backup not found: {}", file_name)))
+}
+
+// Replace the contents of `conn` with a snapshot after checking it is intact
+pub fn restore(conn: &mut Connection, backup_path: &Path) -> AppResult<()> {
+    // SQLite treats truncated files as empty databases, so require tables too
+    let (check, has_tables) = Connection::open_with_flags(backup_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|src| {
+            let check: String = src.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+            Ok((check, has_data(&src)?))
+        })
+        .map_err(|e| AppError::Validation(format!("Backup {} is not readable: {}", backup_path.display(), e)))?;
+    if check != "ok" {
+        return Err(AppError::Validation(format!("Backup {} is damaged: {}", backup_path.display(), check)));
+    }
+    if !has_tables {
+        return Err(AppError::Validation(format!("Backup {} contains no data", backup_path.display())));
+    }
+
+    conn.restore(DatabaseName::Main, backup_path, None::<fn(rusqlite::backup::Progress)>)?;
+    Ok(())
+}
+
+// Whether the database has any tables yet, i.e. is worth backing up
+pub fn has_data(conn: &Connection) -> SqlResult<bool> {
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'", [], |row| row.get::<_, i64>(0))
+        .map(|count| count > 0)
+}
+
+fn backup_files(dir: &Path) -> Vec<PathBuf> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == EXTENSION)
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(PREFIX))
+        })
+        .collect()
+}
+
+fn prune(dir: &Path, keep: usize) {
+    let mut files = backup_files(dir);
+    // Names start with the timestamp, so they sort oldest first
+    files.sort();
+
+    let excess = files.len().saturating_sub(keep);
+    for path in files.into_iter().take(excess) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            eprintln!("Warning: Failed to remove old backup {}: {}", path.display(), e);
+        }
+    }
+}
@@ -0,0 +1,510 @@
+use rusqlite::{params, OptionalExtension, Result as SqlResult, Transaction};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+
+use crate::db::{ApiAuth, Database};
+use crate::error::{AppError, AppResult};
+
+// Portable JSON bundle of everything a user configures in tock-ui: favorites,
+// project and report API routes, cached projects, report settings and
+// preferences. Caches, delivery history and scheduler state are left out.
+//
+// Rows refer to routes by name rather than id so a bundle can be imported
+// into a database where the ids differ.
+
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SettingsBundle {
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub favorites: Vec<BundleFavorite>,
+    #[serde(default)]
+    pub api_routes: Vec<BundleRoute>,
+    #[serde(default)]
+    pub report_api_routes: Vec<BundleRoute>,
+    #[serde(default)]
+    pub cached_projects: Vec<BundleCachedProject>,
+    #[serde(default)]
+    pub report_settings: Option<BundleReportSettings>,
+    #[serde(default)]
+    pub preferences: Vec<BundlePreference>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleFavorite {
+    pub name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleRoute {
+    pub name: String,
+    pub url: String,
+    pub enabled: bool,
+    #[serde(default)]
+    pub auth: ApiAuth,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleCachedProject {
+    pub name: String,
+    pub description: String,
+    pub api_route: Option<String>, // Name of the source API route
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleReportSettings {
+    pub auto_send_enabled: bool,
+    pub api_route: Option<String>, // Name of the selected report API route
+    pub period_type: String,
+    pub cutoff_day: Option<u32>,
+    pub send_partial: bool,
+    pub schedule: crate::schedule::ReportSchedule,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundlePreference {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportMode {
+    // Add what is missing and keep existing rows on conflict
+    Merge,
+    // Delete existing settings first
+    Replace,
+}
+
+impl ImportMode {
+    pub fn parse(value: &str) -> AppResult<Self> {
+        match value {
+            "merge" => Ok(ImportMode::Merge),
+            "replace" => Ok(ImportMode::Replace),
+            other => Err(AppError::Validation(format!("Unknown import mode: {}. Use merge or replace.", other))),
+        }
+    }
+}
+
+// A bundle row that was not imported as-is
+#[derive(Debug, Serialize, Type)]
+pub struct ImportConflict {
+    pub table: String,
+    pub key: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize, Type)]
+pub struct ImportReport {
+    pub favorites: usize,
+    pub api_routes: usize,
+    pub report_api_routes: usize,
+    pub cached_projects: usize,
+    pub preferences: usize,
+    pub report_settings: bool,
+    pub conflicts: Vec<ImportConflict>,
+}
+
+impl ImportReport {
+    fn conflict(&mut self, table: &str, key: impl Into<String>, reason: impl Into<String>) {
+        self.conflicts.push(ImportConflict {
+            table: table.to_string(),
+            key: key.into(),
+            reason: reason.into(),
+        });
+    }
+}
+
+// Build a bundle from the database. Route secrets are masked unless
+// `include_secrets` is set.
+pub fn export(db: &Database, include_secrets: bool) -> SqlResult<SettingsBundle> {
+    let to_bundle_route = |route: &crate::db::ApiRoute| BundleRoute {
+        name: route.name.clone(),
+        url: route.url.clone(),
+        enabled: route.enabled,
+        auth: if include_secrets { route.auth.clone() } else { route.auth.masked() },
+    };
+
+    let api_routes = db.get_all_api_routes()?;
+    let report_api_routes = db.get_all_report_api_routes()?;
+    let route_name = |routes: &[crate::db::ApiRoute], id: Option<i64>| {
+        id.and_then(|id| routes.iter().find(|r| r.id == Some(id)).map(|r| r.name.clone()))
+    };
+
+    let settings = db.get_report_settings()?;
+
+    Ok(SettingsBundle {
+        version: BUNDLE_VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        favorites: db
+            .get_all_favorites()?
+            .into_iter()
+            .map(|f| BundleFavorite { name: f.name, description: f.description })
+            .collect(),
+        api_routes: api_routes.iter().map(to_bundle_route).collect(),
+        report_api_routes: report_api_routes.iter().map(to_bundle_route).collect(),
+        cached_projects: db
+            .get_cached_projects(None)?
+            .into_iter()
+            .map(|p| BundleCachedProject {
+                api_route: route_name(&api_routes, p.source_api_route_id),
+                name: p.name,
+                description: p.description,
+            })
+            .collect(),
+        report_settings: Some(BundleReportSettings {
+            auto_send_enabled: settings.auto_send_enabled,
+            api_route: route_name(&report_api_routes, settings.selected_api_route_id),
+            period_type: settings.period_type,
+            cutoff_day: settings.cutoff_day,
+            send_partial: settings.send_partial,
+            schedule: settings.schedule,
+        }),
+        preferences: db
+            .get_all_preferences()?
+            .into_iter()
+            .map(|(key, value)| BundlePreference { key, value })
+            .collect(),
+    })
+}
+
+// Import a bundle in a single transaction
+pub fn import(db: &Database, bundle: &SettingsBundle, mode: ImportMode) -> AppResult<ImportReport> {
+    if bundle.version > BUNDLE_VERSION {
+        return Err(AppError::Validation(format!(
+            "Settings bundle version {} is newer than this app supports ({})",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+
+    let report = db.transaction(|tx| {
+        let mut report = ImportReport::default();
+
+        // Credentials of replaced routes, used to fill in masked secrets
+        let mut previous_auth = HashMap::new();
+        // Undelivered reports, queued again for the imported routes
+        let mut queued = Vec::new();
+        if mode == ImportMode::Replace {
+            for table in ["api_routes", "report_api_routes"] {
+                previous_auth.insert(table, route_auths(tx, table)?);
+            }
+            queued = queued_reports(tx)?;
+
+            tx.execute_batch(
+                "DELETE FROM favorite_projects;
+                 DELETE FROM cached_projects;
+                 DELETE FROM api_routes;
+                 DELETE FROM report_api_routes;
+                 DELETE FROM user_preferences;
+                 UPDATE report_settings SET selected_api_route_id = NULL, auto_send_enabled = 0;",
+            )?;
+        }
+
+        import_favorites(tx, bundle, &mut report)?;
+        for (table, routes) in [("api_routes", &bundle.api_routes), ("report_api_routes", &bundle.report_api_routes)] {
+            let previous = previous_auth.remove(table).unwrap_or_default();
+            import_routes(tx, table, routes, &previous, &mut report)?;
+        }
+        requeue_reports(tx, &queued, &mut report)?;
+        import_cached_projects(tx, bundle, &mut report)?;
+        import_preferences(tx, bundle, &mut report)?;
+        if let Some(settings) = &bundle.report_settings {
+            import_report_settings(tx, settings, mode, &mut report)?;
+        }
+
+        Ok(report)
+    })?;
+    Ok(report)
+}
+
+fn import_favorites(tx: &Transaction, bundle: &SettingsBundle, report: &mut ImportReport) -> SqlResult<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    for favorite in &bundle.favorites {
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO favorite_projects (name, description, created_at) VALUES (?1, ?2, ?3)",
+            params![favorite.name, favorite.description, now],
+        )?;
+        if inserted == 0 {
+            report.conflict(
+                "favorite_projects",
+                format!("{} / {}", favorite.name, favorite.description),
+                "Favorite already exists",
+            );
+        } else {
+            report.favorites += 1;
+        }
+    }
+    Ok(())
+}
+
+fn route_auths(tx: &Transaction, table: &str) -> SqlResult<HashMap<String, ApiAuth>> {
+    let mut stmt = tx.prepare(&format!("SELECT name, auth_config FROM {}", table))?;
+    let auths = stmt
+        .query_map([], |row| Ok((row.get(0)?, ApiAuth::from_column(row.get(1)?))))?
+        .collect::<SqlResult<HashMap<_, _>>>()?;
+    Ok(auths)
+}
+
+// Routes are matched by name and existing routes are kept. Masked secrets are
+// taken from the replaced route of the same name; without one the route is
+// imported without authentication.
+fn import_routes(
+    tx: &Transaction,
+    table: &str,
+    routes: &[BundleRoute],
+    previous_auth: &HashMap<String, ApiAuth>,
+    report: &mut ImportReport,
+) -> SqlResult<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    for route in routes {
+        if route_id(tx, table, &route.name)?.is_some() {
+            report.conflict(table, &route.name, "A route with this name already exists; kept the existing route");
+            continue;
+        }
+
+        let auth = match previous_auth.get(&route.name) {
+            Some(previous) => route.auth.clone().with_secrets_from(previous),
+            None => route.auth.clone(),
+        };
+        let auth = if auth.has_masked_secrets() {
+            report.conflict(table, &route.name, "Credentials were not included in the bundle; re-enter them");
+            ApiAuth::None
+        } else {
+            auth
+        };
+
+        tx.execute(
+            &format!("INSERT INTO {} (name, url, enabled, created_at, auth_config) VALUES (?1, ?2, ?3, ?4, ?5)", table),
+            params![route.name, route.url, route.enabled as i32, now, auth.to_column()],
+        )?;
+        match table {
+            "api_routes" => report.api_routes += 1,
+            _ => report.report_api_routes += 1,
+        }
+    }
+    Ok(())
+}
+
+// An outbox entry with the name of the route it is queued for. Deleting the
+// routes cascades to the outbox, so entries are kept by value.
+struct QueuedReport {
+    route: Option<String>,
+    period: String,
+    partial: bool,
+    payload: String,
+    attempts: i64,
+    next_attempt_at: Option<String>,
+    last_error: Option<String>,
+    created_at: String,
+}
+
+fn queued_reports(tx: &Transaction) -> SqlResult<Vec<QueuedReport>> {
+    let mut stmt = tx.prepare(
+        "SELECT r.name, o.period, o.partial, o.payload, o.attempts, o.next_attempt_at, o.last_error, o.created_at
+         FROM outbox o LEFT JOIN report_api_routes r ON r.id = o.api_route_id
+         ORDER BY o.id",
+    )?;
+    let queued = stmt
+        .query_map([], |row| {
+            Ok(QueuedReport {
+                route: row.get(0)?,
+                period: row.get(1)?,
+                partial: row.get::<_, i32>(2)? != 0,
+                payload: row.get(3)?,
+                attempts: row.get(4)?,
+                next_attempt_at: row.get(5)?,
+                last_error: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?
+        .collect::<SqlResult<Vec<_>>>()?;
+    Ok(queued)
+}
+
+// Queue reports again for the imported route of the same name. Reports for a
+// route the bundle doesn't have can't be delivered and are dropped.
+fn requeue_reports(tx: &Transaction, queued: &[QueuedReport], report: &mut ImportReport) -> SqlResult<()> {
+    for entry in queued {
+        let route = match &entry.route {
+            Some(name) => route_id(tx, "report_api_routes", name)?,
+            None => None,
+        };
+        match route {
+            Some(id) => {
+                tx.execute(
+                    "INSERT INTO outbox (api_route_id, period, partial, payload, attempts, next_attempt_at, last_error, created_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        id,
+                        entry.period,
+                        entry.partial as i32,
+                        entry.payload,
+                        entry.attempts,
+                        entry.next_attempt_at,
+                        entry.last_error,
+                        entry.created_at,
+                    ],
+                )?;
+            }
+            None => report.conflict(
+                "outbox",
+                &entry.period,
+                format!(
+                    "Undelivered report dropped, its route {} is not in the bundle",
+                    entry.route.as_deref().unwrap_or("(deleted)")
+                ),
+            ),
+        }
+    }
+    Ok(())
+}
+
+fn route_id(tx: &Transaction, table: &str, name: &str) -> SqlResult<Option<i64>> {
+    tx.query_row(&format!("SELECT id FROM {} WHERE name = ?1", table), params![name], |row| row.get(0))
+        .optional()
+}
+
+fn import_cached_projects(tx: &Transaction, bundle: &SettingsBundle, report: &mut ImportReport) -> SqlResult<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    for project in &bundle.cached_projects {
+        let source = match &project.api_route {
+            Some(name) => match route_id(tx, "api_routes", name)? {
+                Some(id) => Some(id),
+                None => {
+                    report.conflict("cached_projects", &project.name, format!("Unknown API route: {}", name));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let inserted = tx.execute(
+            "INSERT OR IGNORE INTO cached_projects (name, description, source_api_route_id, last_synced) VALUES (?1, ?2, ?3, ?4)",
+            params![project.name, project.description, source, now],
+        )?;
+        if inserted == 0 {
+            report.conflict("cached_projects", &project.name, "Project already cached for this route");
+        } else {
+            report.cached_projects += 1;
+        }
+    }
+    Ok(())
+}
+
+fn import_preferences(tx: &Transaction, bundle: &SettingsBundle, report: &mut ImportReport) -> SqlResult<()> {
+    let now = chrono::Local::now().to_rfc3339();
+    for preference in &bundle.preferences {
+        let existing: Option<String> = tx
+            .query_row("SELECT value FROM user_preferences WHERE key = ?1", params![preference.key], |row| row.get(0))
+            .optional()?;
+
+        match existing {
+            Some(value) if value == preference.value => {}
+            Some(_) => report.conflict("user_preferences", &preference.key, "Preference already set; kept the existing value"),
+            None => {
+                tx.execute(
+                    "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                    params![preference.key, preference.value, now],
+                )?;
+                report.preferences += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Report settings are a single row: merge keeps the local settings once a
+// report route has been chosen, replace always overwrites them
+fn import_report_settings(
+    tx: &Transaction,
+    settings: &BundleReportSettings,
+    mode: ImportMode,
+    report: &mut ImportReport,
+) -> SqlResult<()> {
+    let configured: Option<Option<i64>> = tx
+        .query_row("SELECT selected_api_route_id FROM report_settings LIMIT 1", [], |row| row.get(0))
+        .optional()?;
+
+    if mode == ImportMode::Merge && matches!(configured, Some(Some(_))) {
+        report.conflict("report_settings", "report_settings", "Report settings already configured; kept the existing settings");
+        return Ok(());
+    }
+
+    let selected = match &settings.api_route {
+        Some(name) => route_id(tx, "report_api_routes", name)?,
+        None => None,
+    };
+    let now = chrono::Local::now().to_rfc3339();
+    let schedule = serde_json::to_string(&settings.schedule).ok();
+
+    // Which periods were already sent stays with this database, so a
+    // replace doesn't send them again
+    let updated = tx.execute(
+        "UPDATE report_settings SET auto_send_enabled = ?1, selected_api_route_id = ?2, updated_at = ?3,
+            period_type = ?4, cutoff_day = ?5, send_partial = ?6, schedule_config = ?7",
+        params![
+            settings.auto_send_enabled && selected.is_some(),
+            selected,
+            now,
+            settings.period_type,
+            settings.cutoff_day,
+            settings.send_partial as i32,
+            schedule,
+        ],
+    )?;
+    if updated == 0 {
+        tx.execute(
+            "INSERT INTO report_settings
+                (auto_send_enabled, selected_api_route_id, last_sent_at, updated_at, period_type, cutoff_day, send_partial, schedule_config)
+             VALUES (?1, ?2, NULL, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                settings.auto_send_enabled && selected.is_some(),
+                selected,
+                now,
+                settings.period_type,
+                settings.cutoff_day,
+                settings.send_partial as i32,
+                schedule,
+            ],
+        )?;
+    }
+    report.report_settings = true;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_keeps_sent_periods_and_queued_reports() {
+        let db = Database::in_memory().unwrap();
+        let kept = db.add_report_api_route("Kept", "https://example.com/kept", &ApiAuth::None).unwrap();
+        let dropped = db.add_report_api_route("Dropped", "https://example.com/dropped", &ApiAuth::None).unwrap();
+        db.update_report_settings(true, Some(kept)).unwrap();
+        db.update_last_closed_period("2026-09").unwrap();
+        db.update_last_auto_run_at("2026-10-01T08:00:00+00:00").unwrap();
+        db.enqueue_outbox(kept, "2026-10", true, "{}").unwrap();
+        db.enqueue_outbox(dropped, "2026-09", false, "{}").unwrap();
+
+        let mut bundle = export(&db, true).unwrap();
+        bundle.report_api_routes.retain(|route| route.name == "Kept");
+        let report = import(&db, &bundle, ImportMode::Replace).unwrap();
+
+        let settings = db.get_report_settings().unwrap();
+        assert_eq!(settings.last_closed_period.as_deref(), Some("2026-09"));
+        assert_eq!(settings.last_auto_run_at.as_deref(), Some("2026-10-01T08:00:00+00:00"));
+
+        let routes = db.get_all_report_api_routes().unwrap();
+        let new_id = routes.iter().find(|r| r.name == "Kept").and_then(|r| r.id).unwrap();
+        assert_eq!(settings.selected_api_route_id, Some(new_id));
+
+        let outbox = db.get_outbox_entries().unwrap();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!((outbox[0].api_route_id, outbox[0].period.as_str()), (new_id, "2026-10"));
+        assert!(report.conflicts.iter().any(|c| c.table == "outbox" && c.key == "2026-09"));
+    }
+}
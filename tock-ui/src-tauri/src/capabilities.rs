@@ -0,0 +1,342 @@
+use serde::Serialize;
+use specta::Type;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::{AppError, AppResult};
+use crate::process::{self, Limits, RunError};
+use crate::tock_binary::TockBinary;
+
+// What the installed tock can do. The version comes from `tock --version`;
+// subcommands and flags are read from the `--help` output, so features can
+// be hidden or worked around instead of failing with tock's own errors.
+
+// First version that is not supported. A new major version of tock may
+// change the output parsed in parser.rs, so 2.0.0 and later are flagged
+// until that output has been checked against the parser tests.
+// There is no lower bound: the app installs the latest release, and an
+// older one missing a command or flag is caught by the --help checks below
+// rather than by its version.
+const MAX_VERSION: TockVersion = TockVersion { major: 2, minor: 0, patch: 0 };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TockVersion {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl TockVersion {
+    // First version number in `tock --version` output, e.g. "tock version
+    // v1.2.3" or "tock 1.2 (abc123)". None for development builds.
+    pub fn parse(output: &str) -> Option<Self> {
+        output.split_whitespace().find_map(|word| {
+            let word = word.trim_start_matches('v');
+            // Drop pre-release and build suffixes such as "-rc1" or "+dirty"
+            let core = word.split(['-', '+']).next()?;
+            let mut parts = core.split('.');
+            let major = parts.next()?.parse().ok()?;
+            let minor = parts.next()?.parse().ok()?;
+            let patch = match parts.next() {
+                Some(patch) => patch.parse().ok()?,
+                None => 0,
+            };
+            Some(TockVersion { major, minor, patch })
+        })
+    }
+}
+
+impl Compatibility {
+    pub fn of(version: Option<TockVersion>) -> Self {
+        match version {
+            None => Compatibility::Unknown,
+            Some(v) if v >= MAX_VERSION => Compatibility::TooNew,
+            Some(_) => Compatibility::Supported,
+        }
+    }
+}
+
+impl fmt::Display for TockVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// There is deliberately no "too old": the supported range has no lower
+// bound, see MAX_VERSION
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    Supported,
+    TooNew,
+    // The version could not be read, e.g. a development build
+    Unknown,
+}
+
+// Returned by the get_tock_capabilities command
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TockCapabilities {
+    // Executable the capabilities were detected for
+    pub path: String,
+    // What `tock --version` printed
+    pub version_output: String,
+    // Parsed version, null when it could not be read
+    pub version: Option<String>,
+    pub compatibility: Compatibility,
+    // First version that is not supported
+    pub max_version: String,
+    // Subcommands listed by `tock --help`
+    pub commands: Vec<String>,
+    // `tock continue`
+    pub continue_activity: bool,
+    // `tock add --duration`, otherwise the end time is worked out instead
+    pub add_duration: bool,
+    // `tock report --date`, needed for past days
+    pub report_date: bool,
+    // JSON output for reports
+    pub json_output: bool,
+}
+
+// What `tock --version` prints, or why it could not be run
+pub fn version_output(tock: &TockBinary) -> AppResult<String> {
+    let mut cmd = tock.command(&["--version"]);
+    match process::run(&mut cmd, &Limits::timeout(process::CHECK_TIMEOUT)) {
+        Ok(output) if output.status.success() => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        Ok(output) => Err(AppError::TockCommand(String::from_utf8_lossy(&output.stderr).trim().to_string())),
+        Err(RunError::Interrupted(interruption)) => Err(interruption.into_error("tock --version")),
+        Err(RunError::Io(_)) => Err(AppError::TockNotInstalled),
+    }
+}
+
+pub fn detect(tock: &TockBinary) -> AppResult<TockCapabilities> {
+    let version_output = version_output(tock)?;
+    let version = TockVersion::parse(&version_output);
+    let compatibility = Compatibility::of(version);
+
+    let main_help = Help::of(tock, &[]);
+    let add_help = Help::of(tock, &["add"]);
+    let report_help = Help::of(tock, &["report"]);
+
+    Ok(TockCapabilities {
+        path: tock.program().display().to_string(),
+        version_output: version_output.trim().to_string(),
+        version: version.map(|v| v.to_string()),
+        compatibility,
+        max_version: MAX_VERSION.to_string(),
+        commands: main_help.commands(),
+        continue_activity: main_help.has_command("continue"),
+        add_duration: add_help.has_flag("--duration"),
+        report_date: report_help.has_flag("--date"),
+        json_output: report_help.has_flag("--json")
+            || report_help.has_flag("--format")
+            || report_help.has_flag("--output"),
+    })
+}
+
+// `--help` output of tock or one of its subcommands. When there is none,
+// e.g. because help failed, everything is assumed to be available so that
+// nothing is disabled by mistake.
+struct Help(Option<String>);
+
+impl Help {
+    fn of(tock: &TockBinary, subcommand: &[&str]) -> Self {
+        let mut args = subcommand.to_vec();
+        args.push("--help");
+        let text = match process::run(&mut tock.command(&args), &Limits::timeout(process::CHECK_TIMEOUT)) {
+            // Some CLIs print help to stderr
+            Ok(output) => format!(
+                "{}\n{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            Err(_) => String::new(),
+        };
+        Help(Some(text).filter(|t| !t.trim().is_empty()))
+    }
+
+    // Names listed under "Commands:" or "Available Commands:"
+    fn commands(&self) -> Vec<String> {
+        let text = match &self.0 {
+            Some(text) => text,
+            None => return Vec::new(),
+        };
+
+        let mut commands = Vec::new();
+        let mut in_list = false;
+        for line in text.lines() {
+            if line.trim_end().to_lowercase().ends_with("commands:") {
+                in_list = true;
+                continue;
+            }
+            if !in_list {
+                continue;
+            }
+            // The list ends at the first blank or unindented line
+            if line.trim().is_empty() || !line.starts_with(' ') {
+                in_list = false;
+                continue;
+            }
+            if let Some(name) = line.split_whitespace().next() {
+                commands.push(name.trim_end_matches(',').to_string());
+            }
+        }
+        commands
+    }
+
+    // Also true when no command list could be found in the help text
+    fn has_command(&self, name: &str) -> bool {
+        let commands = self.commands();
+        commands.is_empty() || commands.iter().any(|c| c == name)
+    }
+
+    fn has_flag(&self, flag: &str) -> bool {
+        let text = match &self.0 {
+            Some(text) => text,
+            None => return true,
+        };
+        text.split(|c: char| c.is_whitespace() || c == ',' || c == '=' || c == '[' || c == ']')
+            .any(|word| word == flag)
+    }
+}
+
+// Detected capabilities per executable. Only successful detections are kept,
+// so installing tock later is picked up.
+#[derive(Default)]
+pub struct CapabilitiesCache {
+    detected: Mutex<Option<(PathBuf, TockCapabilities)>>,
+}
+
+impl CapabilitiesCache {
+    fn lock(&self) -> MutexGuard<'_, Option<(PathBuf, TockCapabilities)>> {
+        match self.detected.lock() {
+            Ok(guard) => guard,
+            // Only a cached detection is behind the lock, keep using it
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    pub fn get(&self, tock: &TockBinary) -> AppResult<TockCapabilities> {
+        if let Some((path, capabilities)) = self.lock().as_ref() {
+            if path == tock.program() {
+                return Ok(capabilities.clone());
+            }
+        }
+
+        let capabilities = detect(tock)?;
+        *self.lock() = Some((tock.program().to_path_buf(), capabilities.clone()));
+        Ok(capabilities)
+    }
+
+    pub fn clear(&self) {
+        *self.lock() = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Laid out the way cobra, the CLI library tock is built with, prints help
+    const MAIN_HELP: &str = "A simple time tracker
+
+Usage:
+  tock [flags]
+  tock [command]
+
+Available Commands:
+  add         Add a completed activity
+  completion  Generate the autocompletion script for the specified shell
+  current     Show the current activity
+  help        Help about any command
+  last        List recent activities
+  report      Show a report
+  start       Start a new activity
+  stop        Stop the current activity
+
+Flags:
+  -h, --help      help for tock
+  -v, --version   version for tock
+
+Use \"tock [command] --help\" for more information about a command.
+";
+
+    const ADD_HELP: &str = "Add a completed activity
+
+Usage:
+  tock add [flags]
+
+Flags:
+  -d, --description string   Activity description
+  -e, --end string           End time (HH:MM)
+  -h, --help                 help for add
+  -p, --project string       Project name
+  -s, --start string         Start time (HH:MM)
+";
+
+    fn help(text: &str) -> Help {
+        Help(Some(text.to_string()))
+    }
+
+    #[test]
+    fn parses_versions() {
+        let version = |output: &str| TockVersion::parse(output).map(|v| v.to_string());
+        assert_eq!(version("tock version v1.2.3\n").as_deref(), Some("1.2.3"));
+        assert_eq!(version("tock version 0.9").as_deref(), Some("0.9.0"));
+        assert_eq!(version("tock version v1.4.0-rc.1+dirty").as_deref(), Some("1.4.0"));
+        assert_eq!(version("tock version dev"), None);
+        assert_eq!(version(""), None);
+        assert!(TockVersion::parse("v1.10.0").unwrap() > TockVersion::parse("v1.9.9").unwrap());
+        assert!(TockVersion::parse("v2.0.0").unwrap() >= MAX_VERSION);
+    }
+
+    #[test]
+    fn supports_every_version_before_the_maximum() {
+        let of = |output: &str| Compatibility::of(TockVersion::parse(output));
+        // No lower bound: old versions are only limited by their --help
+        assert_eq!(of("tock version v0.0.1"), Compatibility::Supported);
+        assert_eq!(of("tock version v0.1.0"), Compatibility::Supported);
+        assert_eq!(of("tock version v1.99.99"), Compatibility::Supported);
+        assert_eq!(of("tock version v2.0.0"), Compatibility::TooNew);
+        assert_eq!(of("tock version v3.1.0"), Compatibility::TooNew);
+        assert_eq!(of("tock version dev"), Compatibility::Unknown);
+    }
+
+    #[test]
+    fn reads_cobra_command_list() {
+        let main = help(MAIN_HELP);
+        assert_eq!(
+            main.commands(),
+            ["add", "completion", "current", "help", "last", "report", "start", "stop"]
+        );
+        assert!(main.has_command("report"));
+        assert!(!main.has_command("continue"));
+    }
+
+    #[test]
+    fn reads_cobra_flags() {
+        let add = help(ADD_HELP);
+        assert!(add.has_flag("--end"));
+        assert!(!add.has_flag("--duration"));
+        assert!(!add.has_flag("--dur"));
+        assert!(help("      --duration string   Duration, e.g. 1h30m").has_flag("--duration"));
+        assert!(help("  --date=DATE  Day to report").has_flag("--date"));
+    }
+
+    #[test]
+    fn assumes_support_when_help_is_inconclusive() {
+        // No help at all
+        assert!(Help(None).has_command("continue"));
+        assert!(Help(None).has_flag("--duration"));
+        // Help without a recognizable command list
+        assert!(help("COMMANDS:\nadd\ncontinue\n").has_command("continue"));
+        assert!(help("usage: tock <command> [options]\n").has_command("continue"));
+    }
+
+    #[test]
+    fn reads_uppercase_command_header() {
+        let main = help("COMMANDS:\n  add       Add an activity\n  report    Show a report\n");
+        assert_eq!(main.commands(), ["add", "report"]);
+        assert!(!main.has_command("continue"));
+    }
+}
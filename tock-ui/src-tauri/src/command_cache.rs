@@ -0,0 +1,354 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use specta::Type;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::CommandResult;
+
+// Results of read-only tock commands, keyed by their arguments, e.g.
+// "report --date 2026-10-05". Each kind of command is kept for its own time
+// and the least recently used entry is dropped once CAPACITY is reached.
+// Expired entries are also purged in the background by `start_eviction`.
+
+pub const CAPACITY: usize = 512;
+
+// How often expired entries are purged
+const EVICTION_INTERVAL: Duration = Duration::from_secs(60);
+
+// Kind of command a cache key belongs to
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyClass {
+    // `tock current`, whose elapsed time goes stale quickly
+    Current,
+    // `tock last`
+    Recent,
+    // Full reports and reports for today or yesterday
+    Report,
+    // Reports for a day before today, which only change through writes and
+    // external edits, and those invalidate the day explicitly
+    PastDay,
+}
+
+impl KeyClass {
+    fn of(key: &str, today: NaiveDate) -> Self {
+        let args: Vec<&str> = key.split(' ').collect();
+        match args.as_slice() {
+            ["current", ..] => KeyClass::Current,
+            ["last", ..] => KeyClass::Recent,
+            ["report", "--date", date] => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(date) if date < today => KeyClass::PastDay,
+                _ => KeyClass::Report,
+            },
+            _ => KeyClass::Report,
+        }
+    }
+
+    fn ttl(&self) -> Duration {
+        match self {
+            KeyClass::Current => Duration::from_secs(5),
+            KeyClass::Recent => Duration::from_secs(30),
+            KeyClass::Report => Duration::from_secs(60),
+            KeyClass::PastDay => Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+// Counters returned by the get_cache_stats command
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    // Entries dropped to make room for new ones
+    pub evictions: u64,
+    // Entries dropped because their time ran out
+    pub expirations: u64,
+    // Entries dropped because a write or an external change made them stale
+    pub invalidations: u64,
+    // Approximate size of the cached keys and outputs
+    pub memory_bytes: usize,
+}
+
+struct CacheEntry {
+    result: CommandResult,
+    expires_at: Instant,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn size(&self, key: &str) -> usize {
+        key.len()
+            + self.result.output.len()
+            + self.result.error.as_ref().map_or(0, String::len)
+            + std::mem::size_of::<CacheEntry>()
+    }
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, CacheEntry>,
+    // Keys by last use, oldest first
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    memory_bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+    expirations: u64,
+    invalidations: u64,
+}
+
+impl Inner {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_used);
+        self.memory_bytes = self.memory_bytes.saturating_sub(entry.size(key));
+        Some(entry)
+    }
+}
+
+pub struct CommandCache {
+    inner: Mutex<Inner>,
+    capacity: usize,
+}
+
+impl CommandCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner::default()),
+            capacity,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            // Only cached data is behind the lock, so keep using it
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<CommandResult> {
+        let mut inner = self.lock();
+
+        let expired = match inner.entries.get(key) {
+            Some(entry) => entry.expires_at <= Instant::now(),
+            None => {
+                inner.misses += 1;
+                return None;
+            }
+        };
+        if expired {
+            inner.remove(key);
+            inner.expirations += 1;
+            inner.misses += 1;
+            return None;
+        }
+
+        let used = inner.tick();
+        let entry = inner.entries.get_mut(key)?;
+        let previous = std::mem::replace(&mut entry.last_used, used);
+        let result = entry.result.clone();
+        inner.recency.remove(&previous);
+        inner.recency.insert(used, key.to_string());
+        inner.hits += 1;
+        Some(result)
+    }
+
+    pub fn set(&self, key: String, result: CommandResult) {
+        let ttl = KeyClass::of(&key, chrono::Local::now().date_naive()).ttl();
+        let mut inner = self.lock();
+        inner.remove(&key);
+
+        while inner.entries.len() >= self.capacity {
+            let oldest = match inner.recency.values().next() {
+                Some(oldest) => oldest.clone(),
+                None => break,
+            };
+            inner.remove(&oldest);
+            inner.evictions += 1;
+        }
+
+        let used = inner.tick();
+        let entry = CacheEntry {
+            result,
+            expires_at: Instant::now() + ttl,
+            last_used: used,
+        };
+        inner.memory_bytes += entry.size(&key);
+        inner.recency.insert(used, key.clone());
+        inner.entries.insert(key, entry);
+    }
+
+    pub fn invalidate(&self) {
+        self.invalidate_matching(|_| true);
+    }
+
+    // Drop the entries whose key matches
+    pub fn invalidate_matching(&self, affected: impl Fn(&str) -> bool) {
+        let mut inner = self.lock();
+        let keys: Vec<String> = inner.entries.keys().filter(|key| affected(key)).cloned().collect();
+        for key in keys {
+            inner.remove(&key);
+            inner.invalidations += 1;
+        }
+    }
+
+    // Drop every entry whose time ran out
+    pub fn purge_expired(&self) {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        let keys: Vec<String> = inner
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in keys {
+            inner.remove(&key);
+            inner.expirations += 1;
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let inner = self.lock();
+        CacheStats {
+            entries: inner.entries.len(),
+            capacity: self.capacity,
+            hits: inner.hits,
+            misses: inner.misses,
+            evictions: inner.evictions,
+            expirations: inner.expirations,
+            invalidations: inner.invalidations,
+            memory_bytes: inner.memory_bytes,
+        }
+    }
+}
+
+// Purge expired entries periodically so unused keys don't linger
+pub fn start_eviction(cache: &'static CommandCache) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(EVICTION_INTERVAL).await;
+            cache.purge_expired();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(output: &str) -> CommandResult {
+        CommandResult {
+            success: true,
+            output: output.to_string(),
+            error: None,
+            interrupted: None,
+        }
+    }
+
+    fn output(cache: &CommandCache, key: &str) -> Option<String> {
+        cache.get(key).map(|r| r.output)
+    }
+
+    // Let an entry's time run out without waiting for it
+    fn expire(cache: &CommandCache, key: &str) {
+        cache.lock().entries.get_mut(key).unwrap().expires_at = Instant::now();
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = CommandCache::new(2);
+        cache.set("current".to_string(), result("a"));
+        cache.set("last -n 10".to_string(), result("b"));
+        // Using "current" makes "last -n 10" the oldest
+        assert_eq!(output(&cache, "current").as_deref(), Some("a"));
+        cache.set("report --today".to_string(), result("c"));
+
+        assert_eq!(output(&cache, "last -n 10"), None);
+        assert_eq!(output(&cache, "current").as_deref(), Some("a"));
+        assert_eq!(output(&cache, "report --today").as_deref(), Some("c"));
+
+        // Replacing an entry doesn't evict another one
+        cache.set("current".to_string(), result("a2"));
+        assert_eq!(output(&cache, "current").as_deref(), Some("a2"));
+        assert_eq!(output(&cache, "report --today").as_deref(), Some("c"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 2);
+        assert_eq!(stats.capacity, 2);
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.hits, 5);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn keeps_each_kind_of_command_for_its_own_time() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        assert_eq!(KeyClass::of("current", today), KeyClass::Current);
+        assert_eq!(KeyClass::of("last -n 10", today), KeyClass::Recent);
+        assert_eq!(KeyClass::of("report --date 2026-10-16", today), KeyClass::PastDay);
+        assert_eq!(KeyClass::of("report --date 2026-10-17", today), KeyClass::Report);
+        assert_eq!(KeyClass::of("report --date soon", today), KeyClass::Report);
+        assert_eq!(KeyClass::of("report --today", today), KeyClass::Report);
+        assert!(KeyClass::Current.ttl() < KeyClass::Recent.ttl());
+        assert!(KeyClass::Recent.ttl() < KeyClass::Report.ttl());
+        assert!(KeyClass::Report.ttl() < KeyClass::PastDay.ttl());
+
+        let cache = CommandCache::new(8);
+        cache.set("current".to_string(), result("a"));
+        cache.set("report --date 2000-01-01".to_string(), result("b"));
+        let expires_at = |key: &str| cache.lock().entries[key].expires_at;
+        let difference = expires_at("report --date 2000-01-01") - expires_at("current");
+        assert!(difference >= KeyClass::PastDay.ttl() - KeyClass::Current.ttl());
+    }
+
+    #[test]
+    fn drops_expired_entries() {
+        let cache = CommandCache::new(8);
+        cache.set("current".to_string(), result("a"));
+        cache.set("last".to_string(), result("b"));
+        cache.set("report --today".to_string(), result("c"));
+
+        expire(&cache, "current");
+        assert_eq!(output(&cache, "current"), None);
+        expire(&cache, "last");
+        cache.purge_expired();
+        assert_eq!(output(&cache, "report --today").as_deref(), Some("c"));
+
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.expirations, 2);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.hits, 1);
+    }
+
+    #[test]
+    fn invalidates_matching_keys() {
+        let cache = CommandCache::new(8);
+        for key in ["current", "report --date 2026-10-16", "report --date 2026-10-17"] {
+            cache.set(key.to_string(), result(key));
+        }
+        let memory = cache.stats().memory_bytes;
+
+        cache.invalidate_matching(|key| key.ends_with("2026-10-16"));
+        assert_eq!(output(&cache, "report --date 2026-10-16"), None);
+        assert!(output(&cache, "report --date 2026-10-17").is_some());
+        assert!(cache.stats().memory_bytes < memory);
+
+        cache.invalidate();
+        let stats = cache.stats();
+        assert_eq!(stats.entries, 0);
+        assert_eq!(stats.invalidations, 3);
+        assert_eq!(stats.memory_bytes, 0);
+    }
+}
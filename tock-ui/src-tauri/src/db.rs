@@ -1,9 +1,15 @@
-use rusqlite::{Connection, params, Result as SqlResult};
+use rusqlite::{Connection, params, Result as SqlResult, Transaction};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use specta::Type;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::backup::{self, BackupInfo};
+use crate::error::{AppError, AppResult};
+use crate::migrations;
+use crate::schedule::ReportSchedule;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct FavoriteProject {
     pub id: Option<i64>,
     pub name: String,
@@ -11,25 +17,130 @@ pub struct FavoriteProject {
     pub created_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+// Placeholder returned to the frontend in place of stored secrets
+pub const MASKED_SECRET: &str = "********";
+
+// Authentication applied to requests sent to an API route
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiAuth {
+    #[default]
+    None,
+    Bearer { token: String },
+    Basic { username: String, password: String },
+    Header { name: String, value: String },
+    QueryKey { name: String, value: String },
+}
+
+impl ApiAuth {
+    // Copy with every secret replaced by MASKED_SECRET
+    pub fn masked(&self) -> ApiAuth {
+        let mask = |secret: &String| {
+            if secret.is_empty() { String::new() } else { MASKED_SECRET.to_string() }
+        };
+        match self {
+            ApiAuth::None => ApiAuth::None,
+            ApiAuth::Bearer { token } => ApiAuth::Bearer { token: mask(token) },
+            ApiAuth::Basic { username, password } => ApiAuth::Basic {
+                username: username.clone(),
+                password: mask(password),
+            },
+            ApiAuth::Header { name, value } => ApiAuth::Header { name: name.clone(), value: mask(value) },
+            ApiAuth::QueryKey { name, value } => ApiAuth::QueryKey { name: name.clone(), value: mask(value) },
+        }
+    }
+    
+    // Restore secrets the frontend sent back masked from the stored config
+    pub fn with_secrets_from(self, existing: &ApiAuth) -> ApiAuth {
+        let keep = |new: String, old: &String| if new == MASKED_SECRET { old.clone() } else { new };
+        match (self, existing) {
+            (ApiAuth::Bearer { token }, ApiAuth::Bearer { token: old }) => ApiAuth::Bearer { token: keep(token, old) },
+            (ApiAuth::Basic { username, password }, ApiAuth::Basic { password: old, .. }) => ApiAuth::Basic {
+                username,
+                password: keep(password, old),
+            },
+            (ApiAuth::Header { name, value }, ApiAuth::Header { value: old, .. }) => ApiAuth::Header { name, value: keep(value, old) },
+            (ApiAuth::QueryKey { name, value }, ApiAuth::QueryKey { value: old, .. }) => ApiAuth::QueryKey { name, value: keep(value, old) },
+            (auth, _) => auth,
+        }
+    }
+    
+    // Copy with every secret left empty, to be entered again
+    pub fn without_secrets(&self) -> ApiAuth {
+        match self {
+            ApiAuth::None => ApiAuth::None,
+            ApiAuth::Bearer { .. } => ApiAuth::Bearer { token: String::new() },
+            ApiAuth::Basic { username, .. } => ApiAuth::Basic {
+                username: username.clone(),
+                password: String::new(),
+            },
+            ApiAuth::Header { name, .. } => ApiAuth::Header { name: name.clone(), value: String::new() },
+            ApiAuth::QueryKey { name, .. } => ApiAuth::QueryKey { name: name.clone(), value: String::new() },
+        }
+    }
+    
+    // Whether any secret is the MASKED_SECRET placeholder
+    pub fn has_masked_secrets(&self) -> bool {
+        match self {
+            ApiAuth::None => false,
+            ApiAuth::Bearer { token } => token == MASKED_SECRET,
+            ApiAuth::Basic { password, .. } => password == MASKED_SECRET,
+            ApiAuth::Header { value, .. } | ApiAuth::QueryKey { value, .. } => value == MASKED_SECRET,
+        }
+    }
+    
+    pub fn from_column(value: Option<String>) -> ApiAuth {
+        value
+            .and_then(|v| serde_json::from_str(&v).ok())
+            .unwrap_or_default()
+    }
+    
+    pub fn to_column(&self) -> Option<String> {
+        match self {
+            ApiAuth::None => None,
+            auth => serde_json::to_string(auth).ok(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct ApiRoute {
     pub id: Option<i64>,
     pub name: String,
     pub url: String,
     pub enabled: bool,
     pub created_at: String,
+    #[serde(default)]
+    pub auth: ApiAuth,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+impl ApiRoute {
+    // Copy that is safe to hand to the frontend
+    pub fn masked(&self) -> ApiRoute {
+        ApiRoute {
+            auth: self.auth.masked(),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct ReportSettings {
     pub id: Option<i64>,
     pub auto_send_enabled: bool,
     pub selected_api_route_id: Option<i64>,
     pub last_sent_at: Option<String>,
+    pub period_type: String, // "month", "iso_week" or "cutoff"
+    pub cutoff_day: Option<u32>,
+    pub send_partial: bool, // Also send the in-progress period as a partial update
+    pub last_closed_period: Option<String>, // Key of the last closed period delivered
+    #[serde(default)]
+    pub schedule: ReportSchedule,
+    pub last_auto_run_at: Option<String>,
     pub updated_at: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CachedProject {
     pub id: Option<i64>,
     pub name: String,
@@ -38,7 +149,7 @@ pub struct CachedProject {
     pub last_synced: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CalendarCache {
     pub id: Option<i64>,
     pub year_month: String, // Format: "YYYY-MM"
@@ -46,28 +157,119 @@ pub struct CalendarCache {
     pub cached_at: String,
 }
 
+// A report payload waiting to be delivered to a report API route
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct OutboxEntry {
+    pub id: Option<i64>,
+    pub api_route_id: i64,
+    pub period: String, // Period key, e.g. "2026-09" or "2026-W39"
+    pub partial: bool, // In-progress period rather than a closed one
+    pub payload: String, // JSON body sent to the route
+    pub attempts: i64,
+    pub next_attempt_at: Option<String>, // UTC; None once automatic retries are exhausted
+    pub last_error: Option<String>,
+    pub created_at: String,
+}
+
+// One attempt to deliver a report, kept as an audit trail
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct ReportDelivery {
+    pub id: Option<i64>,
+    pub attempted_at: String,
+    pub api_route_id: i64,
+    pub route_name: String,
+    pub route_url: String,
+    pub period: String, // Period key, e.g. "2026-09" or "2026-W39"
+    pub partial: bool,
+    pub payload_hash: String, // SHA-256 of the request body, hex encoded
+    pub success: bool,
+    pub status_code: Option<u16>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+}
+
+// Persisted state of a background scheduler job
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct JobState {
+    pub name: String,
+    pub last_run_at: Option<String>,
+    pub next_run_at: Option<String>, // UTC
+    pub last_success: Option<bool>,
+    pub last_message: Option<String>,
+}
+
+// Environment variable and command line flag that relocate the database,
+// e.g. onto a synced folder. ":memory:" keeps everything in memory.
+pub const DB_PATH_ENV: &str = "TOCK_UI_DB_PATH";
+pub const DB_PATH_FLAG: &str = "--db-path";
+
+const IN_MEMORY: &str = ":memory:";
+
 pub struct Database {
     conn: Mutex<Connection>,
+    path: Option<PathBuf>, // None for in-memory databases
 }
 
 impl Database {
+    // Open the database at the configured location
     pub fn new() -> SqlResult<Self> {
-        let db_path = Self::get_db_path();
+        match Self::configured_path() {
+            Some(path) if path.as_os_str() == IN_MEMORY => Self::in_memory(),
+            Some(path) => Self::open(path),
+            None => Self::open(Self::get_db_path()),
+        }
+    }
+    
+    // Open (or create) a database file and bring its schema up to date
+    pub fn open(path: impl AsRef<Path>) -> SqlResult<Self> {
+        let db_path = path.as_ref().to_path_buf();
         
         // Ensure parent directory exists
         if let Some(parent) = db_path.parent() {
             std::fs::create_dir_all(parent).ok();
         }
         
-        let conn = Connection::open(db_path)?;
+        let conn = Connection::open(&db_path)?;
+        Self::from_connection(conn, Some(db_path))
+    }
+    
+    // A fresh database that lives only as long as this value
+    pub fn in_memory() -> SqlResult<Self> {
+        Self::from_connection(Connection::open_in_memory()?, None)
+    }
+    
+    fn from_connection(conn: Connection, path: Option<PathBuf>) -> SqlResult<Self> {
         let db = Database {
             conn: Mutex::new(conn),
+            path,
         };
         
         db.init_tables()?;
         Ok(db)
     }
     
+    // Location of the database file, None when in memory
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+    
+    // Location override from the command line flag, then the environment
+    fn configured_path() -> Option<PathBuf> {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            if arg == DB_PATH_FLAG {
+                return args.next().map(PathBuf::from);
+            }
+            if let Some(value) = arg.strip_prefix(DB_PATH_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+                return Some(PathBuf::from(value));
+            }
+        }
+        
+        std::env::var_os(DB_PATH_ENV)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    }
+    
     fn get_db_path() -> PathBuf {
         let mut path = dirs::data_local_dir()
             .or_else(|| dirs::home_dir())
@@ -79,96 +281,66 @@ impl Database {
     }
     
     fn init_tables(&self) -> SqlResult<()> {
-        let conn = self.conn.lock().unwrap();
-        
-        // Create favorites table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS favorite_projects (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                description TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                UNIQUE(name, description)
-            )",
-            [],
-        )?;
-        
-        // Create API routes table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS api_routes (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL UNIQUE,
-                url TEXT NOT NULL,
-                enabled INTEGER NOT NULL DEFAULT 1,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
+        let mut conn = self.conn.lock().unwrap();
         
-        // Create report API routes table (separate from api_routes)
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS report_api_routes (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                url TEXT NOT NULL,
-                enabled INTEGER NOT NULL DEFAULT 1,
-                created_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-        
-        // Create report settings table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS report_settings (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                auto_send_enabled INTEGER NOT NULL DEFAULT 0,
-                selected_api_route_id INTEGER,
-                last_sent_at TEXT,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY(selected_api_route_id) REFERENCES report_api_routes(id) ON DELETE SET NULL
-            )",
-            [],
-        )?;
-        
-        // Create cached projects table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS cached_projects (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                description TEXT NOT NULL,
-                source_api_route_id INTEGER,
-                last_synced TEXT NOT NULL,
-                UNIQUE(name, description, source_api_route_id),
-                FOREIGN KEY(source_api_route_id) REFERENCES api_routes(id) ON DELETE CASCADE
-            )",
-            [],
-        )?;
-        
-        // Create calendar cache table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS calendar_cache (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                year_month TEXT NOT NULL UNIQUE,
-                data TEXT NOT NULL,
-                cached_at TEXT NOT NULL
-            )",
-            [],
-        )?;
+        // Snapshot existing data before changing the schema
+        if let Some(path) = &self.path {
+            if migrations::current_version(&conn)? < migrations::latest_version() && backup::has_data(&conn)? {
+                if let Err(e) = backup::create(&conn, path, "pre_migration") {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+        }
         
-        // Create user preferences table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS user_preferences (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                key TEXT NOT NULL UNIQUE,
-                value TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            )",
-            [],
-        )?;
+        migrations::run(&mut conn)?;
+        Ok(())
+    }
+    
+    // Backup methods
+    pub fn backup(&self, reason: &str) -> AppResult<PathBuf> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| AppError::Validation("The in-memory database cannot be backed up".to_string()))?;
+        let conn = self.conn.lock().unwrap();
+        backup::create(&conn, path, reason)
+    }
+    
+    pub fn list_backups(&self) -> Vec<BackupInfo> {
+        match &self.path {
+            Some(path) => backup::list(path),
+            None => Vec::new(),
+        }
+    }
+    
+    // Replace the database with a snapshot from the backup folder. The
+    // current data is backed up first, and the restored schema is migrated.
+    pub fn restore_backup(&self, file_name: &str) -> AppResult<()> {
+        let path = self
+            .path
+            .as_ref()
+            .ok_or_else(|| AppError::NotFound("The in-memory database has no backups".to_string()))?;
+        let source = backup::resolve(path, file_name)?;
         
+        let mut conn = self.conn.lock().unwrap();
+        // Prune only once the chosen snapshot has been restored, it may be
+        // the oldest one
+        backup::snapshot(&conn, path, "pre_restore")?;
+        backup::restore(&mut conn, &source)?;
+        migrations::run(&mut conn)?;
+        backup::prune_old(path);
         Ok(())
     }
     
+    // Run `f` in a transaction, committed only when it returns Ok
+    pub fn transaction<T>(&self, f: impl FnOnce(&Transaction) -> SqlResult<T>) -> SqlResult<T> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        let value = f(&tx)?;
+        tx.commit()?;
+        Ok(value)
+    }
+    
     // Favorite Projects methods
     pub fn add_favorite(&self, name: &str, description: &str) -> SqlResult<i64> {
         let conn = self.conn.lock().unwrap();
@@ -221,26 +393,68 @@ impl Database {
         Ok(favorites)
     }
     
+    // Shared by api_routes and report_api_routes. Authentication is only
+    // changed when provided, and secrets sent back masked keep their stored
+    // value. Stored secrets are never carried over to a new URL: they are
+    // cleared and have to be entered again.
+    fn update_route(
+        conn: &Connection,
+        table: &str,
+        id: i64,
+        route: (&str, &str, bool),
+        auth: Option<&ApiAuth>,
+    ) -> SqlResult<()> {
+        let (name, url, enabled) = route;
+        let existing = conn.query_row(
+            &format!("SELECT url, auth_config FROM {} WHERE id = ?1", table),
+            params![id],
+            |row| Ok((row.get::<_, String>(0)?, ApiAuth::from_column(row.get(1)?))),
+        );
+        let stored = match existing {
+            Ok((stored_url, stored)) if stored_url == url => stored,
+            Ok((_, stored)) => stored.without_secrets(),
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(()),
+            Err(e) => return Err(e),
+        };
+        let auth = match auth {
+            Some(auth) => auth.clone().with_secrets_from(&stored),
+            None => stored,
+        };
+        
+        conn.execute(
+            &format!("UPDATE {} SET name = ?1, url = ?2, enabled = ?3, auth_config = ?4 WHERE id = ?5", table),
+            params![name, url, enabled as i32, auth.to_column(), id],
+        )?;
+        Ok(())
+    }
+    
+    fn map_route(row: &rusqlite::Row) -> SqlResult<ApiRoute> {
+        Ok(ApiRoute {
+            id: Some(row.get(0)?),
+            name: row.get(1)?,
+            url: row.get(2)?,
+            enabled: row.get::<_, i32>(3)? != 0,
+            created_at: row.get(4)?,
+            auth: ApiAuth::from_column(row.get(5)?),
+        })
+    }
+    
     // API Routes methods
-    pub fn add_api_route(&self, name: &str, url: &str) -> SqlResult<i64> {
+    pub fn add_api_route(&self, name: &str, url: &str, auth: &ApiAuth) -> SqlResult<i64> {
         let conn = self.conn.lock().unwrap();
         let now = chrono::Local::now().to_rfc3339();
         
         conn.execute(
-            "INSERT INTO api_routes (name, url, enabled, created_at) VALUES (?1, ?2, 1, ?3)",
-            params![name, url, now],
+            "INSERT INTO api_routes (name, url, enabled, created_at, auth_config) VALUES (?1, ?2, 1, ?3, ?4)",
+            params![name, url, now, auth.to_column()],
         )?;
         
         Ok(conn.last_insert_rowid())
     }
     
-    pub fn update_api_route(&self, id: i64, name: &str, url: &str, enabled: bool) -> SqlResult<()> {
+    pub fn update_api_route(&self, id: i64, name: &str, url: &str, enabled: bool, auth: Option<&ApiAuth>) -> SqlResult<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute(
-            "UPDATE api_routes SET name = ?1, url = ?2, enabled = ?3 WHERE id = ?4",
-            params![name, url, enabled as i32, id],
-        )?;
-        Ok(())
+        Self::update_route(&conn, "api_routes", id, (name, url, enabled), auth)
     }
     
     pub fn delete_api_route(&self, id: i64) -> SqlResult<()> {
@@ -255,43 +469,31 @@ impl Database {
     pub fn get_all_api_routes(&self) -> SqlResult<Vec<ApiRoute>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, url, enabled, created_at FROM api_routes ORDER BY name"
+            "SELECT id, name, url, enabled, created_at, auth_config FROM api_routes ORDER BY name"
         )?;
         
-        let routes = stmt.query_map([], |row| {
-            Ok(ApiRoute {
-                id: Some(row.get(0)?),
-                name: row.get(1)?,
-                url: row.get(2)?,
-                enabled: row.get::<_, i32>(3)? != 0,
-                created_at: row.get(4)?,
-            })
-        })?
+        let routes = stmt.query_map([], Self::map_route)?
         .collect::<SqlResult<Vec<_>>>()?;
         
         Ok(routes)
     }
     
     // Report API Routes methods (separate from regular API routes)
-    pub fn add_report_api_route(&self, name: &str, url: &str) -> SqlResult<i64> {
+    pub fn add_report_api_route(&self, name: &str, url: &str, auth: &ApiAuth) -> SqlResult<i64> {
         let conn = self.conn.lock().unwrap();
         let now = chrono::Local::now().to_rfc3339();
         
         conn.execute(
-            "INSERT INTO report_api_routes (name, url, enabled, created_at) VALUES (?1, ?2, 1, ?3)",
-            params![name, url, now],
+            "INSERT INTO report_api_routes (name, url, enabled, created_at, auth_config) VALUES (?1, ?2, 1, ?3, ?4)",
+            params![name, url, now, auth.to_column()],
         )?;
         
         Ok(conn.last_insert_rowid())
     }
     
-    pub fn update_report_api_route(&self, id: i64, name: &str, url: &str, enabled: bool) -> SqlResult<()> {
+    pub fn update_report_api_route(&self, id: i64, name: &str, url: &str, enabled: bool, auth: Option<&ApiAuth>) -> SqlResult<()> {
         let conn = self.conn.lock().unwrap();
-        conn.execute(
-            "UPDATE report_api_routes SET name = ?1, url = ?2, enabled = ?3 WHERE id = ?4",
-            params![name, url, enabled as i32, id],
-        )?;
-        Ok(())
+        Self::update_route(&conn, "report_api_routes", id, (name, url, enabled), auth)
     }
     
     pub fn delete_report_api_route(&self, id: i64) -> SqlResult<()> {
@@ -306,18 +508,10 @@ impl Database {
     pub fn get_all_report_api_routes(&self) -> SqlResult<Vec<ApiRoute>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
-            "SELECT id, name, url, enabled, created_at FROM report_api_routes ORDER BY name"
+            "SELECT id, name, url, enabled, created_at, auth_config FROM report_api_routes ORDER BY name"
         )?;
         
-        let routes = stmt.query_map([], |row| {
-            Ok(ApiRoute {
-                id: Some(row.get(0)?),
-                name: row.get(1)?,
-                url: row.get(2)?,
-                enabled: row.get::<_, i32>(3)? != 0,
-                created_at: row.get(4)?,
-            })
-        })?
+        let routes = stmt.query_map([], Self::map_route)?
         .collect::<SqlResult<Vec<_>>>()?;
         
         Ok(routes)
@@ -329,7 +523,10 @@ impl Database {
         
         // Try to get existing settings
         let result = conn.query_row(
-            "SELECT id, auto_send_enabled, selected_api_route_id, last_sent_at, updated_at FROM report_settings LIMIT 1",
+            "SELECT id, auto_send_enabled, selected_api_route_id, last_sent_at, updated_at,
+                    period_type, cutoff_day, send_partial, last_closed_period,
+                    schedule_config, last_auto_run_at
+             FROM report_settings LIMIT 1",
             [],
             |row| {
                 Ok(ReportSettings {
@@ -338,6 +535,15 @@ impl Database {
                     selected_api_route_id: row.get(2)?,
                     last_sent_at: row.get(3)?,
                     updated_at: row.get(4)?,
+                    period_type: row.get(5)?,
+                    cutoff_day: row.get(6)?,
+                    send_partial: row.get::<_, i32>(7)? != 0,
+                    last_closed_period: row.get(8)?,
+                    schedule: row
+                        .get::<_, Option<String>>(9)?
+                        .and_then(|json| serde_json::from_str(&json).ok())
+                        .unwrap_or_default(),
+                    last_auto_run_at: row.get(10)?,
                 })
             }
         );
@@ -356,6 +562,12 @@ impl Database {
                     auto_send_enabled: false,
                     selected_api_route_id: None,
                     last_sent_at: None,
+                    period_type: "month".to_string(),
+                    cutoff_day: None,
+                    send_partial: false,
+                    last_closed_period: None,
+                    schedule: ReportSchedule::default(),
+                    last_auto_run_at: None,
                     updated_at: now,
                 })
             },
@@ -389,6 +601,58 @@ impl Database {
         Ok(())
     }
     
+    pub fn update_report_period(&self, period_type: &str, cutoff_day: Option<u32>, send_partial: bool) -> SqlResult<()> {
+        // Make sure the settings row exists
+        self.get_report_settings()?;
+        
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Local::now().to_rfc3339();
+        
+        conn.execute(
+            "UPDATE report_settings SET period_type = ?1, cutoff_day = ?2, send_partial = ?3, updated_at = ?4",
+            params![period_type, cutoff_day, send_partial as i32, now],
+        )?;
+        
+        Ok(())
+    }
+    
+    pub fn update_report_schedule(&self, schedule: &ReportSchedule) -> SqlResult<()> {
+        // Make sure the settings row exists
+        self.get_report_settings()?;
+        
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Local::now().to_rfc3339();
+        let config = serde_json::to_string(schedule).ok();
+        
+        conn.execute(
+            "UPDATE report_settings SET schedule_config = ?1, updated_at = ?2",
+            params![config, now],
+        )?;
+        
+        Ok(())
+    }
+    
+    pub fn update_last_auto_run_at(&self, last_auto_run_at: &str) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE report_settings SET last_auto_run_at = ?1",
+            params![last_auto_run_at],
+        )?;
+        Ok(())
+    }
+    
+    pub fn update_last_closed_period(&self, period: &str) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Local::now().to_rfc3339();
+        
+        conn.execute(
+            "UPDATE report_settings SET last_closed_period = ?1, updated_at = ?2",
+            params![period, now],
+        )?;
+        
+        Ok(())
+    }
+    
     pub fn update_last_sent_at(&self, last_sent_at: &str) -> SqlResult<()> {
         let conn = self.conn.lock().unwrap();
         let now = chrono::Local::now().to_rfc3339();
@@ -401,6 +665,200 @@ impl Database {
         Ok(())
     }
     
+    // Outbox methods
+    fn map_outbox_entry(row: &rusqlite::Row) -> SqlResult<OutboxEntry> {
+        Ok(OutboxEntry {
+            id: Some(row.get(0)?),
+            api_route_id: row.get(1)?,
+            period: row.get(2)?,
+            partial: row.get::<_, i32>(3)? != 0,
+            payload: row.get(4)?,
+            attempts: row.get(5)?,
+            next_attempt_at: row.get(6)?,
+            last_error: row.get(7)?,
+            created_at: row.get(8)?,
+        })
+    }
+    
+    // Queue a payload for immediate delivery. A pending entry for the same
+    // route and period is replaced so only the latest report is sent.
+    pub fn enqueue_outbox(&self, api_route_id: i64, period: &str, partial: bool, payload: &str) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        let now = chrono::Local::now().to_rfc3339();
+        let due = chrono::Utc::now().to_rfc3339();
+        
+        conn.execute(
+            "DELETE FROM outbox WHERE api_route_id = ?1 AND period = ?2 AND partial = ?3",
+            params![api_route_id, period, partial as i32],
+        )?;
+        conn.execute(
+            "INSERT INTO outbox (api_route_id, period, partial, payload, attempts, next_attempt_at, last_error, created_at)
+             VALUES (?1, ?2, ?3, ?4, 0, ?5, NULL, ?6)",
+            params![api_route_id, period, partial as i32, payload, due, now],
+        )?;
+        
+        Ok(conn.last_insert_rowid())
+    }
+    
+    pub fn get_outbox_entry(&self, id: i64) -> SqlResult<Option<OutboxEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT id, api_route_id, period, partial, payload, attempts, next_attempt_at, last_error, created_at
+             FROM outbox WHERE id = ?1",
+            params![id],
+            Self::map_outbox_entry,
+        );
+        
+        match result {
+            Ok(entry) => Ok(Some(entry)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    
+    pub fn get_outbox_entries(&self) -> SqlResult<Vec<OutboxEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, api_route_id, period, partial, payload, attempts, next_attempt_at, last_error, created_at
+             FROM outbox ORDER BY created_at"
+        )?;
+        
+        let entries = stmt.query_map([], Self::map_outbox_entry)?;
+        entries.collect::<SqlResult<Vec<_>>>()
+    }
+    
+    // Entries whose next attempt is at or before `now` (UTC RFC 3339)
+    pub fn get_due_outbox_entries(&self, now: &str) -> SqlResult<Vec<OutboxEntry>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, api_route_id, period, partial, payload, attempts, next_attempt_at, last_error, created_at
+             FROM outbox WHERE next_attempt_at IS NOT NULL AND next_attempt_at <= ?1
+             ORDER BY next_attempt_at"
+        )?;
+        
+        let entries = stmt.query_map(params![now], Self::map_outbox_entry)?;
+        entries.collect::<SqlResult<Vec<_>>>()
+    }
+    
+    pub fn record_outbox_failure(&self, id: i64, attempts: i64, next_attempt_at: Option<&str>, error: &str) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE outbox SET attempts = ?1, next_attempt_at = ?2, last_error = ?3 WHERE id = ?4",
+            params![attempts, next_attempt_at, error, id],
+        )?;
+        Ok(())
+    }
+    
+    pub fn delete_outbox_entry(&self, id: i64) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("DELETE FROM outbox WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+    
+    // Report Delivery history methods
+    pub fn add_report_delivery(&self, delivery: &ReportDelivery) -> SqlResult<i64> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO report_deliveries
+                (attempted_at, api_route_id, route_name, route_url, period, partial, payload_hash, success, status_code, response_body, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                delivery.attempted_at,
+                delivery.api_route_id,
+                delivery.route_name,
+                delivery.route_url,
+                delivery.period,
+                delivery.partial as i32,
+                delivery.payload_hash,
+                delivery.success as i32,
+                delivery.status_code,
+                delivery.response_body,
+                delivery.error,
+            ],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+    
+    // Most recent deliveries first
+    pub fn get_report_deliveries(&self, limit: u32) -> SqlResult<Vec<ReportDelivery>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, attempted_at, api_route_id, route_name, route_url, period, partial, payload_hash, success, status_code, response_body, error
+             FROM report_deliveries ORDER BY id DESC LIMIT ?1"
+        )?;
+        
+        let deliveries = stmt.query_map(params![limit], |row| {
+            Ok(ReportDelivery {
+                id: Some(row.get(0)?),
+                attempted_at: row.get(1)?,
+                api_route_id: row.get(2)?,
+                route_name: row.get(3)?,
+                route_url: row.get(4)?,
+                period: row.get(5)?,
+                partial: row.get::<_, i32>(6)? != 0,
+                payload_hash: row.get(7)?,
+                success: row.get::<_, i32>(8)? != 0,
+                status_code: row.get(9)?,
+                response_body: row.get(10)?,
+                error: row.get(11)?,
+            })
+        })?;
+        
+        deliveries.collect::<SqlResult<Vec<_>>>()
+    }
+    
+    // Scheduler job methods
+    fn map_job_state(row: &rusqlite::Row) -> SqlResult<JobState> {
+        Ok(JobState {
+            name: row.get(0)?,
+            last_run_at: row.get(1)?,
+            next_run_at: row.get(2)?,
+            last_success: row.get::<_, Option<i32>>(3)?.map(|v| v != 0),
+            last_message: row.get(4)?,
+        })
+    }
+    
+    pub fn get_job_state(&self, name: &str) -> SqlResult<Option<JobState>> {
+        let conn = self.conn.lock().unwrap();
+        let result = conn.query_row(
+            "SELECT name, last_run_at, next_run_at, last_success, last_message FROM scheduler_jobs WHERE name = ?1",
+            params![name],
+            Self::map_job_state,
+        );
+        
+        match result {
+            Ok(state) => Ok(Some(state)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    
+    pub fn get_all_job_states(&self) -> SqlResult<Vec<JobState>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT name, last_run_at, next_run_at, last_success, last_message FROM scheduler_jobs ORDER BY name"
+        )?;
+        
+        let states = stmt.query_map([], Self::map_job_state)?;
+        states.collect::<SqlResult<Vec<_>>>()
+    }
+    
+    pub fn save_job_state(&self, state: &JobState) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO scheduler_jobs (name, last_run_at, next_run_at, last_success, last_message)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                state.name,
+                state.last_run_at,
+                state.next_run_at,
+                state.last_success.map(|v| v as i32),
+                state.last_message,
+            ],
+        )?;
+        Ok(())
+    }
+    
     // Calendar Cache methods
     pub fn get_calendar_cache(&self, year_month: &str) -> SqlResult<Option<CalendarCache>> {
         let conn = self.conn.lock().unwrap();
@@ -445,6 +903,16 @@ impl Database {
         Ok(())
     }
     
+    // Clear cached months from `from` to `to`, both "YYYY-MM"
+    pub fn clear_calendar_cache_range(&self, from: &str, to: &str) -> SqlResult<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM calendar_cache WHERE year_month BETWEEN ?1 AND ?2",
+            params![from, to],
+        )?;
+        Ok(())
+    }
+    
     pub fn clear_all_calendar_cache(&self) -> SqlResult<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute("DELETE FROM calendar_cache", [])?;
@@ -529,15 +997,199 @@ impl Database {
         }
     }
     
-    pub fn set_preference(&self, key: &str, value: &str) -> SqlResult<()> {
+    pub fn get_all_preferences(&self) -> SqlResult<Vec<(String, String)>> {
         let conn = self.conn.lock().unwrap();
-        let now = chrono::Local::now().to_rfc3339();
-        
-        conn.execute(
-            "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
-            params![key, value, now],
-        )?;
-        
-        Ok(())
+        let mut stmt = conn.prepare("SELECT key, value FROM user_preferences ORDER BY key")?;
+        let preferences = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<SqlResult<Vec<_>>>()?;
+        Ok(preferences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Empty folder for a test database, removed by the test when done
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tock-ui-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn restores_oldest_of_retained_backups() {
+        let dir = temp_dir("restore-oldest");
+        let db = Database::open(dir.join("tock_ui.db")).unwrap();
+        db.add_favorite("Backed up", "").unwrap();
+
+        // RETENTION snapshots, the first of them the oldest
+        let snapshot = db.backup("manual").unwrap();
+        let backups = backup::backup_dir(db.path().unwrap());
+        for day in 1..=backup::RETENTION {
+            let name = format!("tock_ui_202001{:02}-000000_scheduled.db", day);
+            std::fs::copy(&snapshot, backups.join(name)).unwrap();
+        }
+        std::fs::remove_file(&snapshot).unwrap();
+
+        db.add_favorite("After backup", "").unwrap();
+        db.restore_backup("tock_ui_20200101-000000_scheduled.db").unwrap();
+
+        let favorites = db.get_all_favorites().unwrap();
+        assert_eq!(favorites.len(), 1);
+        assert_eq!(favorites[0].name, "Backed up");
+        // The pre-restore snapshot is kept, the oldest pruned afterwards
+        let remaining = db.list_backups();
+        assert_eq!(remaining.len(), backup::RETENTION);
+        assert!(remaining.iter().any(|b| b.file_name.contains("pre_restore")));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn adds_and_removes_favorites() {
+        let db = Database::in_memory().unwrap();
+        db.add_favorite("Client", "Support").unwrap();
+        // Adding it again keeps a single entry
+        db.add_favorite("Client", "Support").unwrap();
+        assert!(db.is_favorite("Client", "Support").unwrap());
+        assert!(!db.is_favorite("Client", "Meetings").unwrap());
+        assert_eq!(db.get_all_favorites().unwrap().len(), 1);
+
+        db.remove_favorite("Client", "Support").unwrap();
+        assert!(!db.is_favorite("Client", "Support").unwrap());
+        assert!(db.get_all_favorites().unwrap().is_empty());
+    }
+
+    #[test]
+    fn keeps_secrets_sent_back_masked() {
+        let db = Database::in_memory().unwrap();
+        let auth = ApiAuth::Basic { username: "me".to_string(), password: "secret".to_string() };
+        let id = db.add_api_route("Projects", "https://example.com/projects", &auth).unwrap();
+
+        let route = db.get_all_api_routes().unwrap().remove(0);
+        assert_eq!(route.auth, auth);
+        // The frontend only ever sees the masked copy
+        let masked = route.masked();
+        assert_eq!(
+            masked.auth,
+            ApiAuth::Basic { username: "me".to_string(), password: MASKED_SECRET.to_string() }
+        );
+        assert!(masked.auth.has_masked_secrets());
+        assert_eq!(ApiAuth::Bearer { token: String::new() }.masked(), ApiAuth::Bearer { token: String::new() });
+
+        // Saving the masked copy keeps the stored password
+        db.update_api_route(id, "Renamed", &route.url, true, Some(&masked.auth)).unwrap();
+        let route = db.get_all_api_routes().unwrap().remove(0);
+        assert_eq!(route.name, "Renamed");
+        assert_eq!(route.auth, auth);
+
+        // A new password replaces it
+        let changed = ApiAuth::Basic { username: "me".to_string(), password: "new".to_string() };
+        db.update_api_route(id, "Renamed", &route.url, true, Some(&changed)).unwrap();
+        assert_eq!(db.get_all_api_routes().unwrap()[0].auth, changed);
+
+        // A different kind of authentication is taken as sent
+        let bearer = ApiAuth::Bearer { token: MASKED_SECRET.to_string() };
+        assert_eq!(bearer.clone().with_secrets_from(&auth), bearer);
+    }
+
+    #[test]
+    fn clears_secrets_when_url_changes() {
+        let db = Database::in_memory().unwrap();
+        let token = ApiAuth::Bearer { token: "secret".to_string() };
+        let id = db.add_api_route("Projects", "https://example.com/projects", &token).unwrap();
+        let report = db.add_report_api_route("Reports", "https://example.com/reports", &token).unwrap();
+
+        // Toggling keeps the URL and the token
+        db.update_api_route(id, "Projects", "https://example.com/projects", false, None).unwrap();
+        assert_eq!(db.get_all_api_routes().unwrap()[0].auth, token);
+
+        // Without new credentials, or with the masked ones, nothing is sent
+        // to the new host
+        db.update_api_route(id, "Projects", "https://other.example/projects", true, None).unwrap();
+        assert_eq!(db.get_all_api_routes().unwrap()[0].auth, ApiAuth::Bearer { token: String::new() });
+        db.update_report_api_route(report, "Reports", "https://other.example/reports", true, Some(&token.masked()))
+            .unwrap();
+        let route = db.get_all_report_api_routes().unwrap().remove(0);
+        assert_eq!(route.url, "https://other.example/reports");
+        assert_eq!(route.auth, ApiAuth::Bearer { token: String::new() });
+
+        // Credentials entered again are stored
+        let new_token = ApiAuth::Bearer { token: "other".to_string() };
+        db.update_report_api_route(report, "Reports", "https://third.example/reports", true, Some(&new_token))
+            .unwrap();
+        assert_eq!(db.get_all_report_api_routes().unwrap()[0].auth, new_token);
+
+        let basic = ApiAuth::Basic { username: "me".to_string(), password: "secret".to_string() };
+        assert_eq!(
+            basic.without_secrets(),
+            ApiAuth::Basic { username: "me".to_string(), password: String::new() }
+        );
+    }
+
+    #[test]
+    fn retries_outbox_entries_until_exhausted() {
+        let db = Database::in_memory().unwrap();
+        let route = db.add_report_api_route("Reports", "https://example.com/reports", &ApiAuth::None).unwrap();
+        let first = db.enqueue_outbox(route, "2026-09", false, "{}").unwrap();
+        let now = chrono::Utc::now().to_rfc3339();
+        assert_eq!(db.get_due_outbox_entries(&now).unwrap().len(), 1);
+
+        // Queuing the same period again replaces the pending entry
+        let id = db.enqueue_outbox(route, "2026-09", false, "{\"v\":2}").unwrap();
+        assert!(db.get_outbox_entry(first).unwrap().is_none());
+        db.enqueue_outbox(route, "2026-09", true, "{}").unwrap();
+        assert_eq!(db.get_outbox_entries().unwrap().len(), 2);
+
+        // A failed attempt is retried later
+        db.record_outbox_failure(id, 1, Some("2999-01-01T00:00:00+00:00"), "HTTP 503").unwrap();
+        let entry = db.get_outbox_entry(id).unwrap().unwrap();
+        assert_eq!(entry.attempts, 1);
+        assert_eq!(entry.payload, "{\"v\":2}");
+        assert_eq!(entry.last_error.as_deref(), Some("HTTP 503"));
+        let due = db.get_due_outbox_entries(&now).unwrap();
+        assert!(due.iter().all(|e| e.id != Some(id)));
+        assert_eq!(db.get_due_outbox_entries("2999-01-01T00:00:00+00:00").unwrap().len(), 2);
+
+        // Without a next attempt it is never due, but stays in the outbox
+        db.record_outbox_failure(id, 5, None, "HTTP 503").unwrap();
+        assert!(db.get_due_outbox_entries("9999-12-31T23:59:59+00:00").unwrap().iter().all(|e| e.id != Some(id)));
+        assert!(db.get_outbox_entry(id).unwrap().is_some());
+
+        db.delete_outbox_entry(id).unwrap();
+        assert_eq!(db.get_outbox_entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn deleting_report_route_drops_its_outbox() {
+        let db = Database::in_memory().unwrap();
+        let kept = db.add_report_api_route("Kept", "https://example.com/a", &ApiAuth::None).unwrap();
+        let deleted = db.add_report_api_route("Deleted", "https://example.com/b", &ApiAuth::None).unwrap();
+        db.enqueue_outbox(kept, "2026-09", false, "{}").unwrap();
+        db.enqueue_outbox(deleted, "2026-09", false, "{}").unwrap();
+
+        // Removed by ON DELETE CASCADE
+        db.delete_report_api_route(deleted).unwrap();
+        let entries = db.get_outbox_entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].api_route_id, kept);
+    }
+
+    #[test]
+    fn clears_calendar_cache_range() {
+        let db = Database::in_memory().unwrap();
+        for month in ["2025-12", "2026-01", "2026-02", "2026-03"] {
+            db.save_calendar_cache(month, "[]").unwrap();
+        }
+
+        // Both ends are included, across a year boundary
+        db.clear_calendar_cache_range("2025-12", "2026-02").unwrap();
+        for month in ["2025-12", "2026-01", "2026-02"] {
+            assert!(db.get_calendar_cache(month).unwrap().is_none(), "{} still cached", month);
+        }
+        assert!(db.get_calendar_cache("2026-03").unwrap().is_some());
     }
 }
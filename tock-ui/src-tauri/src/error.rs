@@ -0,0 +1,146 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use specta::datatype::{reference::Reference, DataType};
+use specta::{Generics, Type, TypeCollection};
+
+// Error returned by every Tauri command. It reaches the frontend as
+//   {"code": "tock_not_installed", "message": "..."}
+// where `code` is stable and safe to match on, and `message` is for display.
+#[derive(Debug, thiserror::Error)]
+pub enum AppError {
+    #[error("Tock CLI was not found. Please install tock first, or set its location in the preferences.")]
+    TockNotInstalled,
+
+    // tock ran but reported an error
+    #[error("{0}")]
+    TockCommand(String),
+
+    #[error("{0}")]
+    Install(String),
+
+    // A process ran past its timeout and was killed
+    #[error("{0}")]
+    Timeout(String),
+
+    #[error("The operation was cancelled")]
+    Cancelled,
+
+    // Invalid input from the caller
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Network(String),
+
+    #[error("The database is locked by another process. Please try again.")]
+    DatabaseLocked,
+
+    #[error("Database error: {0}")]
+    Database(rusqlite::Error),
+
+    #[error("{0}")]
+    Io(String),
+
+    #[error("Failed to process data: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl AppError {
+    // Stable identifier for the kind of error
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::TockNotInstalled => "tock_not_installed",
+            AppError::TockCommand(_) => "tock_command_failed",
+            AppError::Install(_) => "install_failed",
+            AppError::Timeout(_) => "timeout",
+            AppError::Cancelled => "cancelled",
+            AppError::Validation(_) => "validation",
+            AppError::NotFound(_) => "not_found",
+            AppError::Network(_) => "network",
+            AppError::DatabaseLocked => "database_locked",
+            AppError::Database(_) => "database",
+            AppError::Io(_) => "io",
+            AppError::Serialization(_) => "serialization",
+            AppError::Internal(_) => "internal",
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        match e.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::DatabaseBusy) | Some(rusqlite::ErrorCode::DatabaseLocked) => {
+                AppError::DatabaseLocked
+            }
+            _ => AppError::Database(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        AppError::Io(format!("File error: {}", e))
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        AppError::Network(format!("Request failed: {}", e))
+    }
+}
+
+// What AppError serializes to, so the generated bindings describe it
+#[derive(Type)]
+#[specta(rename = "AppError")]
+#[allow(dead_code)]
+struct AppErrorShape {
+    code: ErrorCode,
+    message: String,
+}
+
+#[derive(Type)]
+#[specta(rename = "AppErrorCode")]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+enum ErrorCode {
+    TockNotInstalled,
+    TockCommandFailed,
+    InstallFailed,
+    Timeout,
+    Cancelled,
+    Validation,
+    NotFound,
+    Network,
+    DatabaseLocked,
+    Database,
+    Io,
+    Serialization,
+    Internal,
+}
+
+impl Type for AppError {
+    fn inline(types: &mut TypeCollection, generics: Generics) -> DataType {
+        AppErrorShape::inline(types, generics)
+    }
+
+    fn reference(types: &mut TypeCollection, generics: &[DataType]) -> Reference {
+        AppErrorShape::reference(types, generics)
+    }
+}
+
+pub type AppResult<T> = Result<T, AppError>;
@@ -0,0 +1,148 @@
+use chrono::{Duration, NaiveDate};
+
+use crate::{backend, parser, Activity};
+
+// Days whose cached activity data a write can change. Writes only touch the
+// activity they create and the one that was running, so everything cached
+// for other days stays valid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    // Smallest range covering every date, None without dates
+    pub fn covering(dates: impl IntoIterator<Item = NaiveDate>) -> Option<Self> {
+        dates.into_iter().fold(None, |range, date| {
+            Some(match range {
+                None => DateRange { start: date, end: date },
+                Some(r) => DateRange {
+                    start: r.start.min(date),
+                    end: r.end.max(date),
+                },
+            })
+        })
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    // First and last "YYYY-MM" calendar cache key in the range
+    pub fn months(&self) -> (String, String) {
+        (self.start.format("%Y-%m").to_string(), self.end.format("%Y-%m").to_string())
+    }
+
+    // Whether a cached tock command, keyed by its arguments, may have read
+    // data in this range. Only day reports can be told apart; `current`,
+    // `last` and full reports always count as affected.
+    pub fn affects_command(&self, key: &str, today: NaiveDate) -> bool {
+        let args: Vec<&str> = key.split(' ').collect();
+        match args.as_slice() {
+            ["report", "--today"] => self.contains(today),
+            ["report", "--yesterday"] => self.contains(today - Duration::days(1)),
+            ["report", "--date", date] => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+                Ok(date) => self.contains(date),
+                Err(_) => true,
+            },
+            _ => true,
+        }
+    }
+}
+
+// Day the running activity started on. Starting, stopping or continuing
+// changes its end, which shows up in the report of that day.
+fn running_since(running: &[Activity]) -> Vec<NaiveDate> {
+    running
+        .iter()
+        .filter_map(|activity| activity.start_time.map(|t| t.date()))
+        .collect()
+}
+
+// Range changed by `start`, `stop` or `continue` at `time`, given what
+// `tock current` printed before the write. None when `time` can't be read.
+pub fn for_switch(current_output: &str, time: Option<&str>, today: NaiveDate) -> Option<DateRange> {
+    let at = backend::resolve_time(time, today)?.date();
+    let mut dates = running_since(&parser::parse_current(current_output));
+    dates.push(at);
+    DateRange::covering(dates)
+}
+
+// Range changed by adding a finished activity
+pub fn for_add(start: &str, end: Option<&str>, duration: Option<&str>, today: NaiveDate) -> Option<DateRange> {
+    let start_time = backend::resolve_time(Some(start), today)?;
+    let end_time = match end {
+        Some(e) => backend::resolve_time(Some(e), start_time.date())?,
+        None => start_time + parser::parse_duration(duration?)?,
+    };
+    DateRange::covering([start_time.date(), end_time.date()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn range(start: &str, end: &str) -> Option<DateRange> {
+        Some(DateRange {
+            start: date(start),
+            end: date(end),
+        })
+    }
+
+    #[test]
+    fn switch_covers_the_running_activity() {
+        let today = date("2026-10-17");
+        // Running since yesterday evening, stopped this morning
+        let current = "2026-10-16 22:30  release  tock-ui  10h0m0s";
+        assert_eq!(for_switch(current, Some("08:30"), today), range("2026-10-16", "2026-10-17"));
+        // Started on the previous day with a time override, nothing running
+        assert_eq!(
+            for_switch("No current activity\n", Some("2026-10-16 17:00"), today),
+            range("2026-10-16", "2026-10-16")
+        );
+        assert_eq!(for_switch("", Some("soon"), today), None);
+    }
+
+    #[test]
+    fn add_covers_start_and_end() {
+        let today = date("2026-10-17");
+        assert_eq!(for_add("09:00", Some("10:00"), None, today), range("2026-10-17", "2026-10-17"));
+        // Over midnight into the next month
+        assert_eq!(
+            for_add("2026-10-31 23:00", None, Some("2h"), today),
+            range("2026-10-31", "2026-11-01")
+        );
+        assert_eq!(
+            for_add("2026-10-31 23:00", Some("2026-11-01 01:00"), None, today),
+            range("2026-10-31", "2026-11-01")
+        );
+        assert_eq!(for_add("09:00", None, None, today), None);
+        assert_eq!(for_add("09:00", None, Some("a while"), today), None);
+    }
+
+    #[test]
+    fn months_and_affected_commands() {
+        let today = date("2026-11-01");
+        let changed = range("2026-10-31", "2026-11-01").unwrap();
+        assert_eq!(changed.months(), ("2026-10".to_string(), "2026-11".to_string()));
+        let year_end = range("2026-12-31", "2027-01-01").unwrap();
+        assert_eq!(year_end.months(), ("2026-12".to_string(), "2027-01".to_string()));
+
+        assert!(changed.affects_command("report --today", today));
+        assert!(changed.affects_command("report --yesterday", today));
+        assert!(changed.affects_command("report --date 2026-10-31", today));
+        assert!(!changed.affects_command("report --date 2026-10-30", today));
+        assert!(!changed.affects_command("report --date 2026-11-02", today));
+        assert!(!range("2026-10-01", "2026-10-30").unwrap().affects_command("report --yesterday", today));
+        // Commands not tied to a day always are
+        assert!(changed.affects_command("current", today));
+        assert!(changed.affects_command("last -n 10", today));
+        assert!(changed.affects_command("report", today));
+        assert!(changed.affects_command("report --date someday", today));
+    }
+}
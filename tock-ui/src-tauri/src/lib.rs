@@ -3,197 +3,229 @@ use std::fs::File;
 use std::io::Write;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::OnceLock;
-use std::sync::Mutex;
-use std::collections::HashMap;
-use std::time::{Duration, Instant};
-use chrono::{NaiveDate, Datelike};
-
-#[cfg(target_os = "windows")]
-use std::os::windows::process::CommandExt;
-
+use chrono::{NaiveDate, NaiveDateTime, Datelike};
+
+mod activity_file;
+mod backend;
+mod backup;
+mod bundle;
+mod capabilities;
+mod command_cache;
 mod db;
-use db::Database;
-
-// Windows-specific constant for process creation optimization
-// CREATE_NO_WINDOW (0x08000000) - Prevents creating a new console window
-// This significantly reduces overhead on Windows
-#[cfg(target_os = "windows")]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+mod error;
+mod invalidation;
+mod migrations;
+mod outbox;
+mod parser;
+mod period;
+mod process;
+mod report;
+mod schedule;
+mod scheduler;
+mod settings;
+mod tock_binary;
+mod watcher;
+use backend::{OnReport, ReportDate, TimeTrackerBackend};
+use capabilities::{CapabilitiesCache, TockCapabilities};
+use command_cache::{CacheStats, CommandCache};
+use db::{ApiAuth, Database};
+use error::{AppError, AppResult};
+use invalidation::DateRange;
+use process::{CancelToken, Interruption, Limits, Operations, RunError};
+use schedule::ReportSchedule;
+use settings::Settings;
+use tock_binary::TockBinary;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use tauri::{AppHandle, Emitter};
+use tauri_specta::ErrorHandlingMode;
 
 static DB: OnceLock<Database> = OnceLock::new();
+static DB_ERROR: OnceLock<String> = OnceLock::new();
 
 fn get_db() -> &'static Database {
     DB.get_or_init(|| {
-        Database::new().expect("Failed to initialize database")
+        match Database::new() {
+            Ok(db) => db,
+            Err(e) => {
+                // Keep the app usable and let the UI report the problem
+                let message = format!("Failed to open database: {}", e);
+                eprintln!("Error: {}. Using a temporary in-memory database.", message);
+                let _ = DB_ERROR.set(message);
+                Database::in_memory().expect("Failed to create in-memory database")
+            }
+        }
     })
 }
 
-// Cache for command results
-struct CacheEntry {
-    result: CommandResult,
-    timestamp: Instant,
-}
+static OPERATIONS: OnceLock<Operations> = OnceLock::new();
 
-struct CommandCache {
-    entries: Mutex<HashMap<String, CacheEntry>>,
-    ttl: Duration,
+fn get_operations() -> &'static Operations {
+    OPERATIONS.get_or_init(Operations::default)
 }
 
-impl CommandCache {
-    fn new(ttl_seconds: u64) -> Self {
-        Self {
-            entries: Mutex::new(HashMap::new()),
-            ttl: Duration::from_secs(ttl_seconds),
-        }
-    }
-
-    fn get(&self, key: &str) -> Option<CommandResult> {
-        let mut entries = match self.entries.lock() {
-            Ok(guard) => guard,
-            Err(poisoned) => {
-                // If the mutex is poisoned, recover the data and continue
-                // This is safe because we're just reading cached data
-                poisoned.into_inner()
-            }
-        };
-        if let Some(entry) = entries.get(key) {
-            if entry.timestamp.elapsed() < self.ttl {
-                return Some(CommandResult {
-                    success: entry.result.success,
-                    output: entry.result.output.clone(),
-                    error: entry.result.error.clone(),
-                });
-            } else {
-                entries.remove(key);
-            }
-        }
-        None
-    }
-
-    fn set(&self, key: String, result: CommandResult) {
-        let mut entries = match self.entries.lock() {
-            Ok(guard) => guard,
-            Err(poisoned) => poisoned.into_inner(),
-        };
-        entries.insert(key, CacheEntry {
-            result,
-            timestamp: Instant::now(),
-        });
-    }
+static CAPABILITIES: OnceLock<CapabilitiesCache> = OnceLock::new();
 
-    fn invalidate(&self) {
-        let mut entries = match self.entries.lock() {
-            Ok(guard) => guard,
-            Err(poisoned) => poisoned.into_inner(),
-        };
-        entries.clear();
-    }
+fn get_capabilities() -> &'static CapabilitiesCache {
+    CAPABILITIES.get_or_init(CapabilitiesCache::default)
 }
 
 static CACHE: OnceLock<CommandCache> = OnceLock::new();
 
 fn get_cache() -> &'static CommandCache {
     CACHE.get_or_init(|| {
-        // Cache for 60 seconds by default
-        CommandCache::new(60)
+        CommandCache::new(command_cache::CAPACITY)
     })
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Type)]
 pub struct Activity {
     pub project: String,
     pub description: String,
-    pub start_time: Option<String>,
-    pub end_time: Option<String>,
-    pub duration: Option<String>,
+    pub start_time: Option<NaiveDateTime>,
+    pub end_time: Option<NaiveDateTime>,
+    pub duration_seconds: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Raw outcome of running a tock command or one of its backend equivalents
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandResult {
     pub success: bool,
     pub output: String,
     pub error: Option<String>,
+    // Set when tock was stopped before it finished
+    #[serde(skip)]
+    pub interrupted: Option<Interruption>,
 }
 
-// Execute tock command with arguments
-fn execute_tock_command(args: Vec<&str>) -> CommandResult {
-    // On Windows, use platform-specific optimizations to reduce command execution time
-    #[cfg(target_os = "windows")]
-    {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NO_WINDOW: u32 = 0x08000000;
-        
-        let output = Command::new("tock")
-            .args(&args)
-            .creation_flags(CREATE_NO_WINDOW) // Prevent console window from flashing
-            .output();
+// Error recorded when the tock executable could not be found
+const TOCK_NOT_FOUND: &str = "Failed to execute tock command. Make sure tock is installed and in your PATH.";
 
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                
-                CommandResult {
-                    success: output.status.success(),
-                    output: stdout,
-                    error: if stderr.is_empty() { None } else { Some(stderr) },
-                }
-            }
-            Err(e) => CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to execute tock command: {}. Make sure tock is installed and in your PATH.", e)),
-            },
+impl CommandResult {
+    // The output of a successful run, otherwise the reported error
+    pub fn into_result(self) -> AppResult<String> {
+        if self.success {
+            return Ok(self.output);
+        }
+        if let Some(interruption) = self.interrupted {
+            return Err(interruption.into_error("tock"));
+        }
+        match self.error {
+            Some(error) if error == TOCK_NOT_FOUND => Err(AppError::TockNotInstalled),
+            Some(error) => Err(AppError::TockCommand(error.trim().to_string())),
+            None => Err(AppError::TockCommand("tock command failed".to_string())),
         }
     }
+}
 
-    // On Unix-like systems, use the standard approach
-    #[cfg(not(target_os = "windows"))]
-    {
-        let output = Command::new("tock")
-            .args(&args)
-            .output();
+fn tock_result(outcome: Result<std::process::Output, RunError>) -> CommandResult {
+    match outcome {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-        match output {
-            Ok(output) => {
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-                let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                
-                CommandResult {
-                    success: output.status.success(),
-                    output: stdout,
-                    error: if stderr.is_empty() { None } else { Some(stderr) },
-                }
+            CommandResult {
+                success: output.status.success(),
+                output: stdout,
+                error: if stderr.is_empty() { None } else { Some(stderr) },
+                interrupted: None,
             }
-            Err(e) => CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to execute tock command: {}. Make sure tock is installed and in your PATH.", e)),
-            },
         }
+        Err(RunError::Io(e)) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(if e.kind() == std::io::ErrorKind::NotFound {
+                TOCK_NOT_FOUND.to_string()
+            } else {
+                format!("Failed to execute tock command: {}", e)
+            }),
+            interrupted: None,
+        },
+        Err(RunError::Interrupted(interruption)) => CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some(interruption.into_error("tock").to_string()),
+            interrupted: Some(interruption),
+        },
     }
 }
 
+// Execute tock command with arguments, stopping it when it exceeds the
+// timeout or gets cancelled
+fn execute_tock_command(tock: &TockBinary, args: Vec<&str>, limits: &Limits) -> CommandResult {
+    tock_result(process::run(&mut tock.command(&args), limits))
+}
+
 // Execute tock command with caching for read operations
-fn execute_tock_command_cached(args: Vec<&str>, use_cache: bool) -> CommandResult {
+fn execute_tock_command_cached(tock: &TockBinary, args: Vec<&str>, use_cache: bool, limits: &Limits) -> CommandResult {
     if use_cache {
         let cache_key = args.join(" ");
         if let Some(cached_result) = get_cache().get(&cache_key) {
             return cached_result;
         }
         
-        let result = execute_tock_command(args.clone());
+        let result = execute_tock_command(tock, args.clone(), limits);
         if result.success {
-            get_cache().set(cache_key, CommandResult {
-                success: result.success,
-                output: result.output.clone(),
-                error: result.error.clone(),
-            });
+            get_cache().set(cache_key, result.clone());
         }
         result
     } else {
-        execute_tock_command(args)
+        execute_tock_command(tock, args, limits)
+    }
+}
+
+// Async version of execute_tock_command_cached for read operations that run
+// many tock commands at once
+async fn execute_tock_command_cached_async(tock: &TockBinary, args: Vec<String>, limits: &Limits) -> CommandResult {
+    let cache_key = args.join(" ");
+    if let Some(cached_result) = get_cache().get(&cache_key) {
+        return cached_result;
+    }
+
+    let cmd = tokio::process::Command::from(tock.command(&args));
+    let result = tock_result(process::run_async(cmd, limits).await);
+    if result.success {
+        get_cache().set(cache_key, result.clone());
+    }
+    result
+}
+
+// Resolve the time tracking backend selected in user preferences
+fn get_backend() -> Box<dyn TimeTrackerBackend> {
+    get_cancellable_backend(CancelToken::default())
+}
+
+// Same as get_backend, but tock commands are stopped once `cancel` is
+// cancelled
+fn get_cancellable_backend(cancel: CancelToken) -> Box<dyn TimeTrackerBackend> {
+    let settings = Settings::load(get_db());
+    let limits = Limits {
+        timeout: settings.tock_timeout(),
+        cancel,
+    };
+    backend::backend_for(
+        Some(settings.tracker_backend.as_str()),
+        activity_file::ActivityFile::locate(settings.activity_file_preference()),
+        backend::CliBackend::new(TockBinary::from_settings(&settings), limits),
+    )
+}
+
+// Invalidate cached activity data for the days a write changed. Without a
+// range, e.g. when a time could not be read, everything is invalidated.
+fn invalidate_caches_for(range: Option<DateRange>) {
+    let range = match range {
+        Some(range) => range,
+        None => return invalidate_all_caches(),
+    };
+    
+    let today = chrono::Local::now().date_naive();
+    get_cache().invalidate_matching(|key| range.affects_command(key, today));
+    
+    let (from, to) = range.months();
+    if let Err(e) = get_db().clear_calendar_cache_range(&from, &to) {
+        eprintln!("Warning: Failed to clear calendar cache from database: {}", e);
     }
 }
 
@@ -208,227 +240,223 @@ fn invalidate_all_caches() {
     }
 }
 
+// Days changed by starting, stopping or continuing at `time`. Has to be
+// worked out before the write, while the running activity is still current.
+fn switch_range(backend: &dyn TimeTrackerBackend, time: Option<&str>) -> Option<DateRange> {
+    // A cached `tock current` can predate an external change. The write
+    // makes it stale anyway, so drop it and ask tock.
+    get_cache().invalidate_matching(|key| key == "current");
+    let current = backend.current();
+    if !current.success {
+        return None;
+    }
+    let today = chrono::Local::now().date_naive();
+    invalidation::for_switch(&current.output, time, today)
+}
+
 #[tauri::command]
-fn start_activity(project: String, description: String, time: Option<String>) -> CommandResult {
-    let mut args = vec!["start", "-p", &project, "-d", &description];
-    
-    if let Some(t) = &time {
-        args.push("-t");
-        args.push(t);
-    }
-    
-    let result = execute_tock_command(args);
+#[specta::specta]
+fn start_activity(project: String, description: String, time: Option<String>) -> AppResult<String> {
+    let backend = get_backend();
+    let changed = switch_range(backend.as_ref(), time.as_deref());
+    let output = backend.start(&project, &description, time.as_deref()).into_result()?;
     
     // Invalidate cache on successful write operation
-    if result.success {
-        invalidate_all_caches();
-    }
+    invalidate_caches_for(changed);
     
-    result
+    Ok(output)
 }
 
 #[tauri::command]
-fn stop_activity(time: Option<String>) -> CommandResult {
-    let mut args = vec!["stop"];
-    
-    if let Some(t) = &time {
-        args.push("-t");
-        args.push(t);
-    }
-    
-    let result = execute_tock_command(args);
+#[specta::specta]
+fn stop_activity(time: Option<String>) -> AppResult<String> {
+    let backend = get_backend();
+    let changed = switch_range(backend.as_ref(), time.as_deref());
+    let output = backend.stop(time.as_deref()).into_result()?;
     
     // Invalidate cache on successful write operation
-    if result.success {
-        invalidate_all_caches();
-    }
+    invalidate_caches_for(changed);
     
-    result
+    Ok(output)
 }
 
 #[tauri::command]
-fn add_activity(project: String, description: String, start: String, end: Option<String>, duration: Option<String>) -> CommandResult {
-    let mut args = vec!["add", "-p", &project, "-d", &description, "-s", &start];
-    
-    if let Some(e) = &end {
-        args.push("-e");
-        args.push(e);
-    } else if let Some(dur) = &duration {
-        args.push("--duration");
-        args.push(dur);
-    }
-    
-    let result = execute_tock_command(args);
+#[specta::specta]
+fn add_activity(project: String, description: String, start: String, end: Option<String>, duration: Option<String>) -> AppResult<String> {
+    let output = get_backend()
+        .add(&project, &description, &start, end.as_deref(), duration.as_deref())
+        .into_result()?;
     
     // Invalidate cache on successful write operation
-    if result.success {
-        invalidate_all_caches();
-    }
+    let today = chrono::Local::now().date_naive();
+    invalidate_caches_for(invalidation::for_add(&start, end.as_deref(), duration.as_deref(), today));
     
-    result
+    Ok(output)
 }
 
 #[tauri::command]
-fn continue_activity(index: Option<u32>, description: Option<String>, project: Option<String>, time: Option<String>) -> CommandResult {
-    let mut args = vec!["continue"];
-    let idx_str: String;
-    
-    if let Some(idx) = index {
-        idx_str = idx.to_string();
-        args.push(&idx_str);
-    }
-    
-    if let Some(d) = &description {
-        args.push("-d");
-        args.push(d);
-    }
-    
-    if let Some(p) = &project {
-        args.push("-p");
-        args.push(p);
-    }
-    
-    if let Some(t) = &time {
-        args.push("-t");
-        args.push(t);
-    }
-    
-    let result = execute_tock_command(args);
+#[specta::specta]
+fn continue_activity(index: Option<u32>, description: Option<String>, project: Option<String>, time: Option<String>) -> AppResult<String> {
+    let backend = get_backend();
+    let changed = switch_range(backend.as_ref(), time.as_deref());
+    let output = backend
+        .continue_activity(index, description.as_deref(), project.as_deref(), time.as_deref())
+        .into_result()?;
     
     // Invalidate cache on successful write operation
-    if result.success {
-        invalidate_all_caches();
-    }
+    invalidate_caches_for(changed);
     
-    result
+    Ok(output)
 }
 
 #[tauri::command]
-fn get_current_activity() -> CommandResult {
-    execute_tock_command_cached(vec!["current"], true)
+#[specta::specta]
+fn get_current_activity() -> AppResult<String> {
+    get_backend().current().into_result()
 }
 
 #[tauri::command]
-fn get_recent_activities(number: Option<u32>) -> CommandResult {
-    let mut args = vec!["last"];
-    let n_str: String;
-    
-    if let Some(n) = number {
-        n_str = n.to_string();
-        args.push("-n");
-        args.push(&n_str);
-    }
-    
-    execute_tock_command_cached(args, true)
+#[specta::specta]
+fn get_recent_activities(number: Option<u32>) -> AppResult<String> {
+    get_backend().last(number).into_result()
 }
 
 #[tauri::command]
-fn get_report(date_type: String, date: Option<String>) -> CommandResult {
-    let mut args = vec!["report"];
-    
-    match date_type.as_str() {
-        "today" => args.push("--today"),
-        "yesterday" => args.push("--yesterday"),
-        "date" => {
-            if let Some(d) = &date {
-                args.push("--date");
-                args.push(d);
-            }
-        }
-        _ => {}
-    }
-    
-    execute_tock_command_cached(args, true)
+#[specta::specta]
+fn get_report(date_type: String, date: Option<String>) -> AppResult<String> {
+    get_backend()
+        .report(&ReportDate::from_request(&date_type, date.as_deref()))
+        .into_result()
+}
+
+#[derive(Debug, Serialize, Type)]
+struct BackendChoice {
+    selected: &'static str,
+    available: Vec<&'static str>,
 }
 
 #[tauri::command]
-fn check_tock_installed() -> CommandResult {
-    let mut cmd = Command::new("tock");
-    cmd.arg("--version");
-    
-    // Windows-specific optimizations
-    #[cfg(target_os = "windows")]
-    {
-        cmd.creation_flags(CREATE_NO_WINDOW);
-    }
-    
-    let output = cmd.output();
+#[specta::specta]
+fn get_available_backends() -> AppResult<BackendChoice> {
+    Ok(BackendChoice {
+        selected: get_backend().name(),
+        available: backend::available_backends(),
+    })
+}
 
-    match output {
-        Ok(output) => {
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            CommandResult {
-                success: output.status.success(),
-                output: stdout,
-                error: None,
-            }
-        }
-        Err(_) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("Tock CLI is not installed or not in PATH. Please install tock first.".to_string()),
+#[tauri::command]
+#[specta::specta]
+fn get_current_activity_structured() -> AppResult<Vec<Activity>> {
+    get_current_activity().map(|output| parser::parse_current(&output))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_recent_activities_structured(number: Option<u32>) -> AppResult<Vec<Activity>> {
+    get_recent_activities(number).map(|output| parser::parse_activity_table(&output))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_report_structured(date_type: String, date: Option<String>) -> AppResult<Vec<Activity>> {
+    let today = chrono::Local::now().date_naive();
+    let report_date = match date_type.as_str() {
+        "today" => today,
+        "yesterday" => today - chrono::Duration::days(1),
+        "date" => match date.as_deref().map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d")) {
+            Some(Ok(d)) => d,
+            _ => return Err(AppError::Validation(
+                "A date in YYYY-MM-DD format is required for date type report".to_string(),
+            )),
         },
+        _ => return Err(AppError::Validation("Invalid date type".to_string())),
+    };
+    
+    get_report(date_type, date).map(|output| parser::parse_report(&output, report_date))
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_activities_for_month_structured(
+    app: AppHandle,
+    year: u32,
+    month: u32,
+    operation_id: Option<String>,
+) -> AppResult<Vec<Activity>> {
+    month_activities(year, month, operation_id.as_deref(), Some(&app))
+        .await
+        .map(|output| parser::parse_month_report(&output))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn check_tock_installed() -> AppResult<String> {
+    capabilities::version_output(&TockBinary::from_settings(&Settings::load(get_db())))
+}
+
+// Version of the installed tock, whether it is supported and which commands
+// and flags it has. Detected once per executable unless `refresh` is set.
+#[tauri::command]
+#[specta::specta]
+fn get_tock_capabilities(refresh: Option<bool>) -> AppResult<TockCapabilities> {
+    if refresh.unwrap_or(false) {
+        get_capabilities().clear();
     }
+    get_capabilities().get(&TockBinary::from_settings(&Settings::load(get_db())))
+}
+
+// Where a tock executable was found, ignoring the path set in preferences
+#[tauri::command]
+#[specta::specta]
+fn detect_tock_path() -> AppResult<Option<String>> {
+    Ok(tock_binary::discover("tock").map(|path| path.display().to_string()))
 }
 
 // Helper function to install tock via Go
-fn install_tock_via_go(error_msg: &str) -> CommandResult {
-    const GO_INSTALL_SUCCESS_MSG: &str = "Tock installed successfully via Go. You may need to restart the application or add Go's bin directory to your PATH.";
-    
+fn install_tock_via_go(error_msg: &str) -> AppResult<String> {
+    // Go may be installed outside the PATH the app was started with
+    let go = match tock_binary::discover("go") {
+        Some(go) => go,
+        None => return Err(AppError::Install(error_msg.to_string())),
+    };
+
     // First check if Go is installed
-    let go_check = Command::new("go")
-        .arg("version")
-        .output();
+    let go_check = process::run(
+        Command::new(&go).arg("version"),
+        &Limits::timeout(process::CHECK_TIMEOUT),
+    );
     
     if go_check.is_err() {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(error_msg.to_string()),
-        };
+        return Err(AppError::Install(error_msg.to_string()));
     }
 
     // Install tock via go install
-    let install_result = Command::new("go")
-        .args(&["install", "github.com/kriuchkov/tock/cmd/tock@latest"])
-        .output();
+    let install_result = process::run(
+        Command::new(&go).args(["install", "github.com/kriuchkov/tock/cmd/tock@latest"]),
+        &Limits::timeout(process::INSTALL_TIMEOUT),
+    );
     
     match install_result {
-        Ok(output) if output.status.success() => {
-            CommandResult {
-                success: true,
-                output: GO_INSTALL_SUCCESS_MSG.to_string(),
-                error: None,
-            }
-        }
+        // The install folder is searched as well, so no PATH changes are needed
+        Ok(output) if output.status.success() => Ok(match tock_binary::discover("tock") {
+            Some(path) => format!("Tock installed successfully via Go at {}", path.display()),
+            None => "Tock installed successfully via Go, but it could not be found. Set its location in Settings → Preferences.".to_string(),
+        }),
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-            CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to install tock: {}", stderr)),
-            }
-        }
-        Err(e) => {
-            CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to execute go install: {}", e)),
-            }
+            Err(AppError::Install(format!("Failed to install tock: {}", stderr)))
         }
+        Err(RunError::Interrupted(interruption)) => Err(interruption.into_error("go install")),
+        Err(RunError::Io(e)) => Err(AppError::Install(format!("Failed to execute go install: {}", e))),
     }
 }
 
 #[tauri::command]
-fn auto_install_tock() -> CommandResult {
+#[specta::specta]
+fn auto_install_tock() -> AppResult<String> {
     // First check if tock is already installed
-    let check_result = check_tock_installed();
-    if check_result.success {
-        return CommandResult {
-            success: true,
-            output: "Tock is already installed".to_string(),
-            error: None,
-        };
+    if check_tock_installed().is_ok() {
+        return Ok("Tock is already installed".to_string());
     }
 
     // Detect the operating system
@@ -437,71 +465,54 @@ fn auto_install_tock() -> CommandResult {
         // Try installing via Homebrew on macOS
         println!("Detected macOS - attempting to install via Homebrew");
         
-        // First check if Homebrew is installed
-        let brew_check = Command::new("brew")
-            .arg("--version")
-            .output();
+        // First check if Homebrew is installed. Apps started from the Finder
+        // don't have its prefix on PATH, so look for it.
+        let brew = tock_binary::discover("brew").unwrap_or_else(|| PathBuf::from("brew"));
+        let brew_check = process::run(
+            Command::new(&brew).arg("--version"),
+            &Limits::timeout(process::CHECK_TIMEOUT),
+        );
         
         if brew_check.is_err() {
-            return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some("Homebrew is not installed. Please install Homebrew first from https://brew.sh".to_string()),
-            };
+            return Err(AppError::Install(
+                "Homebrew is not installed. Please install Homebrew first from https://brew.sh".to_string(),
+            ));
         }
 
         // Add the tap
-        let tap_result = Command::new("brew")
-            .args(&["tap", "kriuchkov/tap"])
-            .output();
+        let tap_result = process::run(
+            Command::new(&brew).args(["tap", "kriuchkov/tap"]),
+            &Limits::timeout(process::INSTALL_TIMEOUT),
+        );
         
         match tap_result {
             Ok(tap_output) if !tap_output.status.success() => {
                 let stderr = String::from_utf8_lossy(&tap_output.stderr).to_string();
-                return CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to add Homebrew tap: {}", stderr)),
-                };
+                return Err(AppError::Install(format!("Failed to add Homebrew tap: {}", stderr)));
             }
-            Err(e) => {
-                return CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to execute brew tap: {}", e)),
-                };
+            Err(RunError::Interrupted(interruption)) => {
+                return Err(interruption.into_error("brew tap"));
+            }
+            Err(RunError::Io(e)) => {
+                return Err(AppError::Install(format!("Failed to execute brew tap: {}", e)));
             }
             _ => {}
         }
 
         // Install tock
-        let install_result = Command::new("brew")
-            .args(&["install", "tock"])
-            .output();
+        let install_result = process::run(
+            Command::new(&brew).args(["install", "tock"]),
+            &Limits::timeout(process::INSTALL_TIMEOUT),
+        );
         
         match install_result {
-            Ok(output) if output.status.success() => {
-                CommandResult {
-                    success: true,
-                    output: "Tock installed successfully via Homebrew".to_string(),
-                    error: None,
-                }
-            }
+            Ok(output) if output.status.success() => Ok("Tock installed successfully via Homebrew".to_string()),
             Ok(output) => {
                 let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-                CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to install tock: {}", stderr)),
-                }
-            }
-            Err(e) => {
-                CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to execute brew install: {}", e)),
-                }
+                Err(AppError::Install(format!("Failed to install tock: {}", stderr)))
             }
+            Err(RunError::Interrupted(interruption)) => Err(interruption.into_error("brew install")),
+            Err(RunError::Io(e)) => Err(AppError::Install(format!("Failed to execute brew install: {}", e))),
         }
     }
 
@@ -521,37 +532,61 @@ fn auto_install_tock() -> CommandResult {
 
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     {
-        CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("Automatic installation is not supported on this operating system. Please install tock manually.".to_string()),
-        }
+        Err(AppError::Install(
+            "Automatic installation is not supported on this operating system. Please install tock manually.".to_string(),
+        ))
     }
 }
 
 #[tauri::command]
-fn get_activities_for_date(date: String) -> CommandResult {
+#[specta::specta]
+fn get_activities_for_date(date: String) -> AppResult<String> {
     // Get report for specific date with caching
-    execute_tock_command_cached(vec!["report", "--date", &date], true)
+    get_backend().report(&ReportDate::Date(date)).into_result()
+}
+
+// Payload of the `month-load-progress` event, emitted as each day of a month
+// load is ready so the calendar can fill in before the whole month is done
+#[derive(Debug, Clone, Serialize, Type)]
+struct MonthLoadProgress {
+    // Id the load was started with, null for loads the app started itself
+    operation_id: Option<String>,
+    date: NaiveDate,
+    // Days ready so far, out of `total`
+    completed: u32,
+    total: u32,
+    // Activities of that day, empty when it has none or its report failed
+    activities: Vec<Activity>,
 }
 
+const MONTH_LOAD_PROGRESS_EVENT: &str = "month-load-progress";
+
 #[tauri::command]
-fn get_activities_for_month(year: u32, month: u32) -> CommandResult {
+#[specta::specta]
+async fn get_activities_for_month(
+    app: AppHandle,
+    year: u32,
+    month: u32,
+    operation_id: Option<String>,
+) -> AppResult<String> {
+    month_activities(year, month, operation_id.as_deref(), Some(&app)).await
+}
+
+// Combined reports of a month, from the calendar cache when it is fresh.
+// With an app handle, progress events are emitted while tock runs.
+async fn month_activities(
+    year: u32,
+    month: u32,
+    operation_id: Option<&str>,
+    app: Option<&AppHandle>,
+) -> AppResult<String> {
     // Validate input parameters
     if year < 1900 || year > 3000 {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Invalid year: {}. Year must be between 1900 and 3000.", year)),
-        };
+        return Err(AppError::Validation(format!("Invalid year: {}. Year must be between 1900 and 3000.", year)));
     }
     
     if month < 1 || month > 12 {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Invalid month: {}. Month must be between 1 and 12.", month)),
-        };
+        return Err(AppError::Validation(format!("Invalid month: {}. Month must be between 1 and 12.", month)));
     }
     
     // Format year-month for database cache key
@@ -585,26 +620,14 @@ fn get_activities_for_month(year: u32, month: u32) -> CommandResult {
             };
             
             if cache_valid {
-                return CommandResult {
-                    success: true,
-                    output: cache_entry.data,
-                    error: None,
-                };
+                return Ok(cache_entry.data);
             }
         }
     }
     
     // Calculate the start and end dates of the month
-    let first_day = match NaiveDate::from_ymd_opt(year as i32, month, 1) {
-        Some(date) => date,
-        None => {
-            return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Invalid year ({}) or month ({})", year, month)),
-            };
-        }
-    };
+    let first_day = NaiveDate::from_ymd_opt(year as i32, month, 1)
+        .ok_or_else(|| AppError::Validation(format!("Invalid year ({}) or month ({})", year, month)))?;
     
     // Calculate last day of month by finding the first day of next month and subtracting one day
     let (next_year, next_month) = if month == 12 {
@@ -613,160 +636,150 @@ fn get_activities_for_month(year: u32, month: u32) -> CommandResult {
         (year, month + 1)
     };
     
-    let last_day = match NaiveDate::from_ymd_opt(next_year as i32, next_month, 1) {
-        Some(next_month_start) => match next_month_start.pred_opt() {
-            Some(d) => d,
-            None => {
-                return CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some("Failed to calculate last day of month".to_string()),
-                };
-            }
-        },
-        None => {
-            return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to calculate next month date for {}-{}", next_year, next_month)),
-            };
+    let last_day = NaiveDate::from_ymd_opt(next_year as i32, next_month, 1)
+        .and_then(|next_month_start| next_month_start.pred_opt())
+        .ok_or_else(|| AppError::Internal(format!("Failed to calculate last day of {}-{}", year, month)))?;
+    
+    // Fetch all activities for each day in the month and aggregate. With an
+    // operation id the frontend can cancel the load through cancel_operation.
+    let operation = operation_id.map(|id| get_operations().begin(id));
+    let cancel = operation.as_ref().map(|o| o.token()).unwrap_or_default();
+    let backend = get_cancellable_backend(cancel);
+
+    let total = last_day.day();
+    let completed = AtomicU32::new(0);
+    let on_report = |date: NaiveDate, result: &CommandResult| {
+        let app = match app {
+            Some(app) => app,
+            None => return,
+        };
+        let progress = MonthLoadProgress {
+            operation_id: operation_id.map(str::to_string),
+            date,
+            completed: completed.fetch_add(1, Ordering::SeqCst) + 1,
+            total,
+            activities: if result.success { parser::parse_report(&result.output, date) } else { Vec::new() },
+        };
+        if let Err(e) = app.emit(MONTH_LOAD_PROGRESS_EVENT, &progress) {
+            eprintln!("Warning: Failed to emit {} event: {}", MONTH_LOAD_PROGRESS_EVENT, e);
         }
     };
+    let combined_output = combined_daily_reports(backend.as_ref(), first_day, last_day, &on_report).await?;
+    
+    // Save to database calendar cache for persistent caching
+    if let Err(e) = get_db().save_calendar_cache(&year_month, &combined_output) {
+        eprintln!("Warning: Failed to save calendar cache to database: {}", e);
+    }
     
-    // Fetch all activities for each day in the month and aggregate
+    Ok(combined_output)
+}
+
+// Reports for every day in [start, end], each preceded by a
+// "=== YYYY-MM-DD ===" header. Days without activities are left out. Fails
+// when tock was stopped, since the days after it would be missing.
+async fn combined_daily_reports(
+    backend: &dyn TimeTrackerBackend,
+    start: NaiveDate,
+    end: NaiveDate,
+    on_report: OnReport<'_>,
+) -> AppResult<String> {
+    if let Some(reason) = backend.daily_reports_unavailable() {
+        return Err(AppError::TockCommand(reason));
+    }
+
     let mut all_outputs = Vec::new();
-    let mut current_date = first_day;
     
-    while current_date <= last_day {
-        let date_str = current_date.format("%Y-%m-%d").to_string();
-        let result = execute_tock_command_cached(vec!["report", "--date", &date_str], true);
-        
+    for (day, result) in backend.daily_reports(start, end, on_report).await {
+        if result.interrupted.is_some() {
+            return result.into_result();
+        }
         if result.success && !result.output.trim().is_empty() {
             // Add date header and the output
-            all_outputs.push(format!("=== {} ===\n{}", date_str, result.output));
+            all_outputs.push(format!("=== {} ===\n{}", day.format("%Y-%m-%d"), result.output));
         }
-        
-        // Move to next day, break if we can't (edge case at max date)
-        current_date = match current_date.succ_opt() {
-            Some(date) => date,
-            None => {
-                // Log warning but don't fail - we've processed up to this point
-                // Use println since this is a backend warning and Tauri captures stdout
-                println!("Warning: Could not increment date beyond {}. This may indicate we've reached the maximum date value.", current_date);
-                break;
-            }
-        };
-    }
-    
-    let combined_output = if all_outputs.is_empty() {
-        String::new()
-    } else {
-        all_outputs.join("\n\n")
-    };
-    
-    // Save to database calendar cache for persistent caching
-    if let Err(e) = get_db().save_calendar_cache(&year_month, &combined_output) {
-        eprintln!("Warning: Failed to save calendar cache to database: {}", e);
     }
     
-    CommandResult {
-        success: true,
-        output: combined_output,
-        error: None,
-    }
+    Ok(all_outputs.join("\n\n"))
+}
+
+// Stop a running operation started with this id, e.g. a month load the
+// user navigated away from. Returns whether such an operation was running.
+#[tauri::command]
+#[specta::specta]
+fn cancel_operation(operation_id: String) -> AppResult<bool> {
+    Ok(get_operations().cancel(&operation_id))
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn get_range_report(start_date: String, end_date: String) -> AppResult<report::RangeReport> {
+    let (start, end) = report::parse_range(&start_date, &end_date).map_err(AppError::Validation)?;
+    let rounding = Settings::load(get_db()).rounding;
+    let backend = get_backend();
+    report::RangeReport::from_daily_reports(start, end, backend.daily_reports(start, end, &|_, _| {}).await, &rounding)
 }
 
 #[tauri::command]
-fn save_report_to_file(
+#[specta::specta]
+async fn save_report_to_file(
     date_type: String,
     date: Option<String>,
     start_date: Option<String>,
     end_date: Option<String>,
     custom_path: Option<String>,
-) -> CommandResult {
-    // Generate report output
-    let mut args = vec!["report"];
-    
-    let report_result = match date_type.as_str() {
-        "today" => {
-            args.push("--today");
-            execute_tock_command(args)
-        }
+    format: Option<String>,
+) -> AppResult<String> {
+    let export_format = report::ExportFormat::parse(format.as_deref().unwrap_or("txt")).map_err(AppError::Validation)?;
+    
+    // Resolve the period covered by the report
+    let today = chrono::Local::now().date_naive();
+    let range = match date_type.as_str() {
+        "today" => Ok((today, today)),
         "yesterday" => {
-            args.push("--yesterday");
-            execute_tock_command(args)
-        }
-        "date" => {
-            if let Some(d) = &date {
-                args.push("--date");
-                args.push(d);
-                execute_tock_command(args)
-            } else {
-                return CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some("Date is required for date type report".to_string()),
-                };
-            }
-        }
-        "range" => {
-            // For range, we'll generate multiple reports
-            // Note: Current implementation uses start date only
-            // Full range reporting would require iterating through dates
-            // and aggregating results, which is not implemented yet
-            if start_date.is_none() || end_date.is_none() {
-                return CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some("Start and end dates are required for range report".to_string()),
-                };
-            }
-            // For simplicity, just use start date
-            args.push("--date");
-            args.push(start_date.as_ref().unwrap());
-            execute_tock_command(args)
-        }
-        _ => {
-            return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some("Invalid date type".to_string()),
-            };
+            let yesterday = today - chrono::Duration::days(1);
+            Ok((yesterday, yesterday))
         }
+        "date" => match &date {
+            Some(d) => report::parse_range(d, d),
+            None => Err("Date is required for date type report".to_string()),
+        },
+        "range" => match (&start_date, &end_date) {
+            // Aggregate every day between start and end into one report
+            (Some(s), Some(e)) => report::parse_range(s, e),
+            _ => Err("Start and end dates are required for range report".to_string()),
+        },
+        _ => Err("Invalid date type".to_string()),
+    };
+    let (start, end) = range.map_err(AppError::Validation)?;
+    
+    // Generate report output. Single-day text reports keep the backend's own
+    // output unless durations are rounded; everything else is rendered from
+    // the parsed activities.
+    let rounding = Settings::load(get_db()).rounding;
+    let backend = get_backend();
+    let content = if export_format == report::ExportFormat::Text && start == end && rounding.minutes == 0 {
+        backend
+            .report(&ReportDate::Date(start.format("%Y-%m-%d").to_string()))
+            .into_result()?
+    } else {
+        report::RangeReport::from_daily_reports(start, end, backend.daily_reports(start, end, &|_, _| {}).await, &rounding)?
+            .render(export_format)?
     };
-
-    if !report_result.success {
-        return report_result;
-    }
 
     // Generate filename
-    let filename = match date_type.as_str() {
-        "today" => {
-            let now = chrono::Local::now();
-            format!("{}_tock.txt", now.format("%Y-%m-%d"))
-        }
-        "yesterday" => {
-            let yesterday = chrono::Local::now() - chrono::Duration::days(1);
-            format!("{}_tock.txt", yesterday.format("%Y-%m-%d"))
-        }
-        "date" => {
-            if let Some(d) = &date {
-                format!("{}_tock.txt", d)
-            } else {
-                "report_tock.txt".to_string()
-            }
-        }
-        "range" => {
-            if let (Some(start), Some(end)) = (&start_date, &end_date) {
-                format!("{}_{}_tock.txt", start, end)
-            } else {
-                "range_tock.txt".to_string()
-            }
-        }
-        _ => "report_tock.txt".to_string(),
+    let period = if start == end {
+        start.format("%Y-%m-%d").to_string()
+    } else {
+        format!("{}_{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
     };
+    let filename = format!("{}_tock.{}", period, export_format.extension());
+
+    let filepath = write_export_file(custom_path, &filename, &content)?;
+    Ok(format!("Report saved to: {}", filepath.display()))
+}
 
-    // Determine save directory
+// Write an exported file into `custom_path`, or the home directory when unset
+fn write_export_file(custom_path: Option<String>, filename: &str, content: &str) -> AppResult<PathBuf> {
     let save_dir = if let Some(path) = custom_path {
         PathBuf::from(path)
     } else {
@@ -777,641 +790,756 @@ fn save_report_to_file(
         }
     };
     
-    let filepath = save_dir.join(&filename);
+    let filepath = save_dir.join(filename);
     
-    match File::create(&filepath) {
-        Ok(mut file) => {
-            if let Err(e) = file.write_all(report_result.output.as_bytes()) {
-                CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to write to file: {}", e)),
-                }
-            } else {
-                CommandResult {
-                    success: true,
-                    output: format!("Report saved to: {}", filepath.display()),
-                    error: None,
-                }
-            }
-        }
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to create file: {}", e)),
-        },
-    }
+    let mut file = File::create(&filepath)
+        .map_err(|e| AppError::Io(format!("Failed to create file {}: {}", filepath.display(), e)))?;
+    file.write_all(content.as_bytes())
+        .map_err(|e| AppError::Io(format!("Failed to write to file {}: {}", filepath.display(), e)))?;
+    
+    Ok(filepath)
 }
 
 // Favorites commands
 #[tauri::command]
-fn add_favorite(name: String, description: String) -> CommandResult {
-    match get_db().add_favorite(&name, &description) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Added to favorites".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to add favorite: {}", e)),
-        },
-    }
+#[specta::specta]
+fn add_favorite(name: String, description: String) -> AppResult<()> {
+    get_db().add_favorite(&name, &description)?;
+    Ok(())
 }
 
 #[tauri::command]
-fn remove_favorite(name: String, description: String) -> CommandResult {
-    match get_db().remove_favorite(&name, &description) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Removed from favorites".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to remove favorite: {}", e)),
-        },
-    }
+#[specta::specta]
+fn remove_favorite(name: String, description: String) -> AppResult<()> {
+    Ok(get_db().remove_favorite(&name, &description)?)
 }
 
 #[tauri::command]
-fn is_favorite(name: String, description: String) -> CommandResult {
-    match get_db().is_favorite(&name, &description) {
-        Ok(is_fav) => CommandResult {
-            success: true,
-            output: if is_fav { "true" } else { "false" }.to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: "false".to_string(),
-            error: Some(format!("Failed to check favorite: {}", e)),
-        },
-    }
+#[specta::specta]
+fn is_favorite(name: String, description: String) -> AppResult<bool> {
+    Ok(get_db().is_favorite(&name, &description)?)
 }
 
 #[tauri::command]
-fn get_all_favorites() -> CommandResult {
-    match get_db().get_all_favorites() {
-        Ok(favorites) => {
-            match serde_json::to_string(&favorites) {
-                Ok(json) => CommandResult {
-                    success: true,
-                    output: json,
-                    error: None,
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to serialize favorites: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get favorites: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_all_favorites() -> AppResult<Vec<db::FavoriteProject>> {
+    Ok(get_db().get_all_favorites()?)
 }
 
 // API Routes commands
 #[tauri::command]
-fn add_api_route(name: String, url: String) -> CommandResult {
-    match get_db().add_api_route(&name, &url) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "API route added".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to add API route: {}", e)),
-        },
-    }
+#[specta::specta]
+fn add_api_route(name: String, url: String, auth: Option<ApiAuth>) -> AppResult<()> {
+    get_db().add_api_route(&name, &url, &auth.unwrap_or_default())?;
+    Ok(())
 }
 
 #[tauri::command]
-fn update_api_route(id: i64, name: String, url: String, enabled: bool) -> CommandResult {
-    match get_db().update_api_route(id, &name, &url, enabled) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "API route updated".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to update API route: {}", e)),
-        },
+#[specta::specta]
+fn update_api_route(id: i64, name: String, url: String, enabled: bool, auth: Option<ApiAuth>) -> AppResult<()> {
+    check_route_auth(&get_db().get_all_api_routes()?, id, &url, auth.as_ref())?;
+    Ok(get_db().update_api_route(id, &name, &url, enabled, auth.as_ref())?)
+}
+
+// Masked credentials only stand for the stored ones while the URL stays the
+// same. For a new URL they have to be entered again.
+fn check_route_auth(routes: &[db::ApiRoute], id: i64, url: &str, auth: Option<&ApiAuth>) -> AppResult<()> {
+    let moved = routes.iter().any(|r| r.id == Some(id) && r.url != url);
+    if moved && auth.is_some_and(ApiAuth::has_masked_secrets) {
+        return Err(AppError::Validation(
+            "The URL changed. Enter the credentials again to use them with the new URL.".to_string(),
+        ));
     }
+    Ok(())
 }
 
 #[tauri::command]
-fn delete_api_route(id: i64) -> CommandResult {
-    match get_db().delete_api_route(id) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "API route deleted".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to delete API route: {}", e)),
-        },
-    }
+#[specta::specta]
+fn delete_api_route(id: i64) -> AppResult<()> {
+    Ok(get_db().delete_api_route(id)?)
 }
 
 #[tauri::command]
-fn get_all_api_routes() -> CommandResult {
-    match get_db().get_all_api_routes() {
-        Ok(routes) => {
-            match serde_json::to_string(&routes) {
-                Ok(json) => CommandResult {
-                    success: true,
-                    output: json,
-                    error: None,
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to serialize API routes: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get API routes: {}", e)),
-        },
+#[specta::specta]
+fn get_all_api_routes() -> AppResult<Vec<db::ApiRoute>> {
+    // Never hand stored secrets back to the frontend
+    Ok(get_db().get_all_api_routes()?.iter().map(|r| r.masked()).collect())
+}
+
+// Apply a route's authentication to an outgoing request
+fn apply_auth(request: reqwest::RequestBuilder, auth: &ApiAuth) -> reqwest::RequestBuilder {
+    match auth {
+        ApiAuth::None => request,
+        ApiAuth::Bearer { token } => request.bearer_auth(token),
+        ApiAuth::Basic { username, password } => request.basic_auth(username, Some(password)),
+        ApiAuth::Header { name, value } => request.header(name.as_str(), value.as_str()),
+        ApiAuth::QueryKey { name, value } => request.query(&[(name, value)]),
     }
 }
 
-// Fetch projects from API route
+// Fetch projects from API route. When `api_route_id` is given, the stored
+// authentication of that route is applied to the request.
 #[tauri::command]
-async fn fetch_projects_from_api(url: String) -> CommandResult {
+#[specta::specta]
+async fn fetch_projects_from_api(url: String, api_route_id: Option<i64>) -> AppResult<String> {
+    let auth = match api_route_id {
+        Some(id) => {
+            let route = get_db()
+                .get_all_api_routes()?
+                .into_iter()
+                .find(|r| r.id == Some(id))
+                .ok_or_else(|| AppError::NotFound("API route not found".to_string()))?;
+            // A route's credentials are only ever sent to the route's own URL
+            if route.url != url {
+                return Err(AppError::Validation(format!(
+                    "URL does not match the API route \"{}\"",
+                    route.name
+                )));
+            }
+            route.auth
+        }
+        None => ApiAuth::None,
+    };
+    
+    fetch_projects(&url, &auth).await
+}
+
+async fn fetch_projects(url: &str, auth: &ApiAuth) -> AppResult<String> {
     // Basic URL validation
     if !url.starts_with("http://") && !url.starts_with("https://") {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("Invalid URL: must start with http:// or https://".to_string()),
-        };
+        return Err(AppError::Validation("Invalid URL: must start with http:// or https://".to_string()));
     }
     
     // Create client with timeout and size limits
-    let client = match reqwest::Client::builder()
+    let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
-        .build() {
-            Ok(c) => c,
-            Err(e) => return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to create HTTP client: {}", e)),
-            },
-        };
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to create HTTP client: {}", e)))?;
     
-    match client.get(&url).send().await {
-        Ok(response) => {
-            // Check response size to prevent memory exhaustion
-            if let Some(content_length) = response.content_length() {
-                if content_length > 10_000_000 { // 10MB limit
-                    return CommandResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some("Response too large (max 10MB)".to_string()),
-                    };
-                }
-            }
-            
-            match response.text().await {
-                Ok(text) => {
-                    // Additional size check after reading
-                    if text.len() > 10_000_000 {
-                        return CommandResult {
-                            success: false,
-                            output: String::new(),
-                            error: Some("Response too large (max 10MB)".to_string()),
-                        };
-                    }
-                    CommandResult {
-                        success: true,
-                        output: text,
-                        error: None,
-                    }
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to read response: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to fetch from API: {}", e)),
-        },
+    let response = apply_auth(client.get(url), auth)
+        .send()
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to fetch from API: {}", e)))?;
+    
+    // Check response size to prevent memory exhaustion
+    if let Some(content_length) = response.content_length() {
+        if content_length > 10_000_000 { // 10MB limit
+            return Err(AppError::Network("Response too large (max 10MB)".to_string()));
+        }
+    }
+    
+    let text = response
+        .text()
+        .await
+        .map_err(|e| AppError::Network(format!("Failed to read response: {}", e)))?;
+    
+    // Additional size check after reading
+    if text.len() > 10_000_000 {
+        return Err(AppError::Network("Response too large (max 10MB)".to_string()));
     }
+    Ok(text)
 }
 
 // Report API Routes commands (separate from regular API routes)
 #[tauri::command]
-fn add_report_api_route(name: String, url: String) -> CommandResult {
-    match get_db().add_report_api_route(&name, &url) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Report API route added".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to add report API route: {}", e)),
-        },
-    }
+#[specta::specta]
+fn add_report_api_route(name: String, url: String, auth: Option<ApiAuth>) -> AppResult<()> {
+    get_db().add_report_api_route(&name, &url, &auth.unwrap_or_default())?;
+    Ok(())
 }
 
 #[tauri::command]
-fn update_report_api_route(id: i64, name: String, url: String, enabled: bool) -> CommandResult {
-    match get_db().update_report_api_route(id, &name, &url, enabled) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Report API route updated".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to update report API route: {}", e)),
-        },
-    }
+#[specta::specta]
+fn update_report_api_route(id: i64, name: String, url: String, enabled: bool, auth: Option<ApiAuth>) -> AppResult<()> {
+    check_route_auth(&get_db().get_all_report_api_routes()?, id, &url, auth.as_ref())?;
+    Ok(get_db().update_report_api_route(id, &name, &url, enabled, auth.as_ref())?)
 }
 
 #[tauri::command]
-fn delete_report_api_route(id: i64) -> CommandResult {
-    match get_db().delete_report_api_route(id) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Report API route deleted".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to delete report API route: {}", e)),
-        },
-    }
+#[specta::specta]
+fn delete_report_api_route(id: i64) -> AppResult<()> {
+    Ok(get_db().delete_report_api_route(id)?)
 }
 
 #[tauri::command]
-fn get_all_report_api_routes() -> CommandResult {
-    match get_db().get_all_report_api_routes() {
-        Ok(routes) => {
-            match serde_json::to_string(&routes) {
-                Ok(json) => CommandResult {
-                    success: true,
-                    output: json,
-                    error: None,
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to serialize report API routes: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get report API routes: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_all_report_api_routes() -> AppResult<Vec<db::ApiRoute>> {
+    // Never hand stored secrets back to the frontend
+    Ok(get_db().get_all_report_api_routes()?.iter().map(|r| r.masked()).collect())
 }
 
 // Report Settings commands
 #[tauri::command]
-fn get_report_settings() -> CommandResult {
-    match get_db().get_report_settings() {
-        Ok(settings) => {
-            match serde_json::to_string(&settings) {
-                Ok(json) => CommandResult {
-                    success: true,
-                    output: json,
-                    error: None,
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to serialize report settings: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get report settings: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_report_settings() -> AppResult<db::ReportSettings> {
+    Ok(get_db().get_report_settings()?)
 }
 
 #[tauri::command]
-fn update_report_settings(auto_send_enabled: bool, selected_api_route_id: Option<i64>) -> CommandResult {
-    match get_db().update_report_settings(auto_send_enabled, selected_api_route_id) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Report settings updated".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to update report settings: {}", e)),
-        },
+#[specta::specta]
+fn update_report_settings(auto_send_enabled: bool, selected_api_route_id: Option<i64>) -> AppResult<()> {
+    Ok(get_db().update_report_settings(auto_send_enabled, selected_api_route_id)?)
+}
+
+// Reporting period configured in the report settings. Invalid settings fall
+// back to calendar months.
+fn report_period_kind(settings: &db::ReportSettings) -> period::PeriodKind {
+    match period::PeriodKind::from_settings(&settings.period_type, settings.cutoff_day) {
+        Ok(kind) => kind,
+        Err(e) => {
+            eprintln!("Warning: {}. Falling back to monthly periods.", e);
+            period::PeriodKind::Month
+        }
     }
 }
 
-#[tauri::command]
-async fn send_monthly_report_to_api(api_route_id: i64) -> CommandResult {
-    // Get the report API route
-    let routes = match get_db().get_all_report_api_routes() {
-        Ok(r) => r,
-        Err(e) => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get report API routes: {}", e)),
+// Build the report for a period, queue it and try to deliver it right away
+async fn send_period_report(
+    api_route_id: i64,
+    kind: period::PeriodKind,
+    report_period: &period::ReportPeriod,
+    partial: bool,
+) -> AppResult<String> {
+    // Make sure the report API route exists
+    let routes = get_db().get_all_report_api_routes()?;
+    if !routes.iter().any(|r| r.id == Some(api_route_id)) {
+        return Err(AppError::NotFound("Report API route not found".to_string()));
+    }
+    
+    // Prepare JSON payload. "year" and "month" refer to the month the period
+    // ends in and are kept for existing receivers.
+    let payload = serde_json::json!({
+        "year": report_period.end.year(),
+        "month": report_period.end.month(),
+        "period": {
+            "type": kind.as_str(),
+            "key": report_period.key,
+            "start": report_period.start.format("%Y-%m-%d").to_string(),
+            "end": report_period.end.format("%Y-%m-%d").to_string(),
         },
-    };
+        "partial": partial,
+        "report": combined_daily_reports(get_backend().as_ref(), report_period.start, report_period.end, &|_, _| {}).await?,
+        "generated_at": chrono::Local::now().to_rfc3339(),
+    });
     
-    let api_route = match routes.iter().find(|r| r.id == Some(api_route_id)) {
-        Some(route) => route,
-        None => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("Report API route not found".to_string()),
-        },
+    // Queue the payload first so it survives a failed delivery
+    let entry_id = get_db().enqueue_outbox(api_route_id, &report_period.key, partial, &payload.to_string())?;
+    
+    let label = if partial {
+        format!("Partial report for {}", report_period.key)
+    } else {
+        format!("Report for {}", report_period.key)
     };
     
-    // Get current month's report
-    let now = chrono::Local::now();
-    let year = now.year() as u32;
-    let month = now.month();
+    let entry = get_db()
+        .get_outbox_entry(entry_id)?
+        .ok_or_else(|| AppError::Internal("Queued report disappeared before it could be sent".to_string()))?;
+    match deliver_outbox_entry(&entry).await {
+        Ok(route_name) => Ok(format!("{} sent successfully to {}", label, route_name)),
+        Err(e) => Err(AppError::Network(format!(
+            "{}. The report was queued and will be retried automatically.",
+            e
+        ))),
+    }
+}
+
+// Send a report right away. By default the period in progress is sent as a
+// partial update; `closed` sends the last closed period instead.
+#[tauri::command]
+#[specta::specta]
+async fn send_monthly_report_to_api(api_route_id: i64, closed: Option<bool>) -> AppResult<String> {
+    let settings = get_db().get_report_settings()?;
     
-    let report_result = get_activities_for_month(year, month);
+    let kind = report_period_kind(&settings);
+    let today = chrono::Local::now().date_naive();
     
-    if !report_result.success {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to generate monthly report: {}", report_result.error.unwrap_or_default())),
-        };
+    if closed.unwrap_or(false) {
+        send_period_report(api_route_id, kind, &kind.last_closed(today), false).await
+    } else {
+        send_period_report(api_route_id, kind, &kind.containing(today), true).await
     }
+}
+
+#[tauri::command]
+#[specta::specta]
+fn update_report_period_settings(period_type: String, cutoff_day: Option<u32>, send_partial: bool) -> AppResult<()> {
+    // Validate before saving
+    period::PeriodKind::from_settings(&period_type, cutoff_day).map_err(AppError::Validation)?;
     
-    // Prepare JSON payload
-    let payload = serde_json::json!({
-        "year": year,
-        "month": month,
-        "report": report_result.output,
-        "generated_at": chrono::Local::now().to_rfc3339(),
-    });
+    Ok(get_db().update_report_period(&period_type, cutoff_day, send_partial)?)
+}
+
+// Longest response body kept in the delivery history
+const MAX_RESPONSE_BODY_CHARS: usize = 4096;
+
+// What happened when a report was POSTed to a route
+struct PostOutcome {
+    status_code: Option<u16>,
+    response_body: Option<String>,
+    error: Option<String>,
+}
+
+// POST a JSON payload to a report API route
+async fn post_report(api_route: &db::ApiRoute, payload: &str) -> PostOutcome {
+    let failed = |error: String| PostOutcome {
+        status_code: None,
+        response_body: None,
+        error: Some(error),
+    };
     
-    // Send to API
     let client = match reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(30))
         .build() {
             Ok(c) => c,
-            Err(e) => return CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to create HTTP client: {}", e)),
-            },
+            Err(e) => return failed(format!("Failed to create HTTP client: {}", e)),
         };
     
-    match client.post(&api_route.url)
-        .json(&payload)
+    let response = match apply_auth(client.post(&api_route.url), &api_route.auth)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(payload.to_string())
         .send()
         .await {
-            Ok(response) => {
-                let status = response.status();
-                if status.is_success() {
-                    // Update last_sent_at timestamp
-                    let sent_at = chrono::Local::now().to_rfc3339();
-                    if let Err(e) = get_db().update_last_sent_at(&sent_at) {
-                        eprintln!("Warning: Failed to update last_sent_at: {}", e);
-                    }
-                    
-                    CommandResult {
-                        success: true,
-                        output: format!("Monthly report sent successfully to {}", api_route.name),
-                        error: None,
-                    }
-                } else {
-                    CommandResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some(format!("API returned status: {}", status)),
-                    }
+            Ok(r) => r,
+            Err(e) => return failed(format!("Failed to send report to API: {}", e)),
+        };
+    
+    let status = response.status();
+    let response_body = response
+        .text()
+        .await
+        .ok()
+        .map(|body| body.chars().take(MAX_RESPONSE_BODY_CHARS).collect::<String>())
+        .filter(|body| !body.is_empty());
+    
+    PostOutcome {
+        status_code: Some(status.as_u16()),
+        response_body,
+        error: if status.is_success() {
+            None
+        } else {
+            Some(format!("API returned status: {}", status))
+        },
+    }
+}
+
+// Hex encoded SHA-256 of a payload, stored in the delivery history
+fn payload_hash(payload: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(payload.as_bytes()))
+}
+
+// Attempt delivery of one outbox entry. Every attempt is recorded in the
+// delivery history. Delivered entries are removed from the outbox; failed ones
+// are rescheduled according to the retry policy. Returns the route name on
+// success.
+async fn deliver_outbox_entry(entry: &db::OutboxEntry) -> Result<String, String> {
+    let id = entry.id.ok_or_else(|| "Outbox entry has no id".to_string())?;
+    
+    let routes = get_db()
+        .get_all_report_api_routes()
+        .map_err(|e| format!("Failed to get report API routes: {}", e))?;
+    
+    let result = match routes.iter().find(|r| r.id == Some(entry.api_route_id)) {
+        Some(api_route) => {
+            let attempted_at = chrono::Local::now().to_rfc3339();
+            let outcome = post_report(api_route, &entry.payload).await;
+            
+            let delivery = db::ReportDelivery {
+                id: None,
+                attempted_at,
+                api_route_id: entry.api_route_id,
+                route_name: api_route.name.clone(),
+                route_url: api_route.url.clone(),
+                period: entry.period.clone(),
+                partial: entry.partial,
+                payload_hash: payload_hash(&entry.payload),
+                success: outcome.error.is_none(),
+                status_code: outcome.status_code,
+                response_body: outcome.response_body,
+                error: outcome.error.clone(),
+            };
+            if let Err(e) = get_db().add_report_delivery(&delivery) {
+                eprintln!("Warning: Failed to record report delivery: {}", e);
+            }
+            
+            match outcome.error {
+                None => Ok(api_route.name.clone()),
+                Some(error) => Err(error),
+            }
+        },
+        None => Err("Report API route not found".to_string()),
+    };
+    
+    match &result {
+        Ok(_) => {
+            if let Err(e) = get_db().delete_outbox_entry(id) {
+                eprintln!("Warning: Failed to remove delivered outbox entry: {}", e);
+            }
+            
+            // Update last_sent_at timestamp
+            let sent_at = chrono::Local::now().to_rfc3339();
+            if let Err(e) = get_db().update_last_sent_at(&sent_at) {
+                eprintln!("Warning: Failed to update last_sent_at: {}", e);
+            }
+            
+            // A closed period is only ever sent once to the auto-send route.
+            // Sending it to another route by hand doesn't count.
+            if !entry.partial && is_auto_send_route(entry.api_route_id) {
+                if let Err(e) = get_db().update_last_closed_period(&entry.period) {
+                    eprintln!("Warning: Failed to update last_closed_period: {}", e);
                 }
-            },
-            Err(e) => CommandResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to send report to API: {}", e)),
-            },
+            }
+        }
+        Err(error) => {
+            let attempts = entry.attempts + 1;
+            let next_attempt_at = outbox::next_attempt_at(attempts, chrono::Utc::now());
+            if let Err(e) = get_db().record_outbox_failure(id, attempts, next_attempt_at.as_deref(), error) {
+                eprintln!("Warning: Failed to update outbox entry: {}", e);
+            }
         }
+    }
+    
+    result
+}
+
+fn is_auto_send_route(api_route_id: i64) -> bool {
+    match get_db().get_report_settings() {
+        Ok(settings) => settings.selected_api_route_id == Some(api_route_id),
+        Err(e) => {
+            eprintln!("Warning: Failed to read report settings: {}", e);
+            false
+        }
+    }
+}
+
+// Retry every outbox entry that is due. Returns (delivered, failed) counts.
+async fn process_due_outbox() -> (usize, usize) {
+    let now = chrono::Utc::now().to_rfc3339();
+    let entries = match get_db().get_due_outbox_entries(&now) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Warning: Failed to read outbox: {}", e);
+            return (0, 0);
+        }
+    };
+    
+    let mut delivered = 0;
+    let mut failed = 0;
+    for entry in &entries {
+        match deliver_outbox_entry(entry).await {
+            Ok(_) => delivered += 1,
+            Err(_) => failed += 1,
+        }
+    }
+    
+    (delivered, failed)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_outbox() -> AppResult<Vec<db::OutboxEntry>> {
+    Ok(get_db().get_outbox_entries()?)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn retry_outbox_entry(id: i64) -> AppResult<String> {
+    let entry = get_db()
+        .get_outbox_entry(id)?
+        .ok_or_else(|| AppError::NotFound("Outbox entry not found".to_string()))?;
+    
+    let route_name = deliver_outbox_entry(&entry).await.map_err(AppError::Network)?;
+    Ok(format!("Report for {} sent successfully to {}", entry.period, route_name))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_report_delivery_history(limit: Option<u32>) -> AppResult<Vec<db::ReportDelivery>> {
+    Ok(get_db().get_report_deliveries(limit.unwrap_or(100))?)
 }
 
 #[tauri::command]
-async fn check_and_send_auto_report() -> CommandResult {
+#[specta::specta]
+fn discard_outbox_entry(id: i64) -> AppResult<()> {
+    Ok(get_db().delete_outbox_entry(id)?)
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn check_and_send_auto_report() -> AppResult<String> {
+    // Deliver reports still waiting in the outbox from earlier failures
+    let (_, failed) = process_due_outbox().await;
+    if failed > 0 {
+        eprintln!("Warning: {} queued report(s) could not be delivered", failed);
+    }
+    
     // Get report settings
-    let settings = match get_db().get_report_settings() {
-        Ok(s) => s,
-        Err(e) => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get report settings: {}", e)),
-        },
-    };
+    let settings = get_db().get_report_settings()?;
     
     // Check if auto-send is enabled
     if !settings.auto_send_enabled {
-        return CommandResult {
-            success: true,
-            output: "Auto-send is not enabled".to_string(),
-            error: None,
-        };
+        return Ok("Auto-send is not enabled".to_string());
     }
     
     // Check if an API route is selected
     let api_route_id = match settings.selected_api_route_id {
         Some(id) => id,
-        None => return CommandResult {
-            success: true,
-            output: "No API route selected for auto-send".to_string(),
-            error: None,
-        },
+        None => return Ok("No API route selected for auto-send".to_string()),
     };
     
-    // Check if we should send (7+ days since last send, or never sent)
-    let should_send = match settings.last_sent_at {
-        None => true, // Never sent, so send now
-        Some(last_sent) => {
-            match chrono::DateTime::parse_from_rfc3339(&last_sent) {
-                Ok(last_sent_dt) => {
-                    let now = chrono::Local::now();
-                    let duration = now.signed_duration_since(last_sent_dt);
-                    duration.num_days() >= 7
-                },
-                Err(_) => true, // If we can't parse, send anyway
-            }
+    let kind = report_period_kind(&settings);
+    let now = chrono::Local::now();
+    
+    // Only run when the schedule says so. The first run happens right away.
+    if let Some(next_due) = next_report_due(&settings, kind) {
+        let next = next_due.map_err(|e| AppError::Validation(format!("Invalid report schedule: {}", e)))?;
+        if next > now {
+            return Ok(format!("Next automatic report is due at {}", next.format("%Y-%m-%d %H:%M")));
         }
-    };
+    }
     
-    if !should_send {
-        return CommandResult {
-            success: true,
-            output: "Not enough time has passed since last auto-send (minimum 7 days)".to_string(),
-            error: None,
-        };
+    if let Err(e) = get_db().update_last_auto_run_at(&now.to_rfc3339()) {
+        eprintln!("Warning: Failed to update last_auto_run_at: {}", e);
     }
     
-    // Send the report
-    send_monthly_report_to_api(api_route_id).await
+    // Reports already queued for this route. One that is still retrying is
+    // left to the outbox instead of being regenerated on every check.
+    let queued: Vec<db::OutboxEntry> = match get_db().get_outbox_entries() {
+        Ok(entries) => entries.into_iter().filter(|e| e.api_route_id == api_route_id).collect(),
+        Err(e) => {
+            eprintln!("Warning: Failed to read outbox: {}", e);
+            Vec::new()
+        }
+    };
+    let queued_for = |key: &str, partial: bool| queued.iter().find(|e| e.period == key && e.partial == partial);
+    
+    // Send the last closed period once
+    let today = now.date_naive();
+    let closed = kind.last_closed(today);
+    let (report_period, partial) = if settings.last_closed_period.as_deref() != Some(closed.key.as_str()) {
+        (closed, false)
+    } else if settings.send_partial {
+        // Send a partial report for the period in progress
+        (kind.containing(today), true)
+    } else {
+        return Ok(with_stuck_note(format!("Report for {} was already sent", closed.key), &queued));
+    };
+    
+    let message = match queued_for(&report_period.key, partial) {
+        Some(entry) if entry.next_attempt_at.is_some() => {
+            format!("A report for {} is still queued for delivery", report_period.key)
+        }
+        // Ran out of automatic retries, sending it again would fail the same way
+        Some(entry) => {
+            return Err(AppError::Network(format!(
+                "The report for {} could not be delivered after {} attempts: {}. Retry or discard it in the outbox.",
+                report_period.key,
+                entry.attempts,
+                entry.last_error.as_deref().unwrap_or("unknown error")
+            )));
+        }
+        None => send_period_report(api_route_id, kind, &report_period, partial).await?,
+    };
+    Ok(with_stuck_note(message, &queued))
+}
+
+// Mention queued reports that ran out of automatic retries, which are only
+// sent again when retried by hand
+fn with_stuck_note(message: String, queued: &[db::OutboxEntry]) -> String {
+    let stuck: Vec<&str> = queued
+        .iter()
+        .filter(|e| e.next_attempt_at.is_none())
+        .map(|e| e.period.as_str())
+        .collect();
+    if stuck.is_empty() {
+        return message;
+    }
+    format!(
+        "{}. Reports for {} could not be delivered and need to be retried or discarded in the outbox.",
+        message,
+        stuck.join(", ")
+    )
+}
+
+// Next scheduled automatic run after the previous one. None if auto-send has
+// never run, in which case it is due immediately.
+fn next_report_due(
+    settings: &db::ReportSettings,
+    kind: period::PeriodKind,
+) -> Option<Result<chrono::DateTime<chrono::Local>, String>> {
+    let last_run = settings
+        .last_auto_run_at
+        .as_deref()
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())?
+        .with_timezone(&chrono::Local);
+    Some(settings.schedule.next_after(last_run, kind))
+}
+
+#[derive(Debug, Serialize, Type)]
+struct NextReportDue {
+    auto_send_enabled: bool,
+    schedule: ReportSchedule,
+    last_run_at: Option<String>,
+    next_due_at: String,
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_next_report_due() -> AppResult<NextReportDue> {
+    let settings = get_db().get_report_settings()?;
+    
+    let next_due_at = match next_report_due(&settings, report_period_kind(&settings)) {
+        Some(Ok(next)) => next.to_rfc3339(),
+        None => chrono::Local::now().to_rfc3339(),
+        Some(Err(e)) => return Err(AppError::Validation(format!("Invalid report schedule: {}", e))),
+    };
+    
+    Ok(NextReportDue {
+        auto_send_enabled: settings.auto_send_enabled,
+        schedule: settings.schedule,
+        last_run_at: settings.last_auto_run_at,
+        next_due_at,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+fn update_report_schedule(schedule: ReportSchedule) -> AppResult<()> {
+    schedule.validate().map_err(AppError::Validation)?;
+    Ok(get_db().update_report_schedule(&schedule)?)
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_scheduler_jobs() -> AppResult<Vec<db::JobState>> {
+    Ok(get_db().get_all_job_states()?)
+}
+
+#[derive(Debug, Serialize, Type)]
+struct DatabaseInfo {
+    path: Option<String>,
+    in_memory: bool,
+    // Why the configured database could not be opened, if it failed
+    error: Option<String>,
+}
+
+#[tauri::command]
+#[specta::specta]
+fn get_database_info() -> AppResult<DatabaseInfo> {
+    let db = get_db();
+    Ok(DatabaseInfo {
+        path: db.path().map(|p| p.to_string_lossy().to_string()),
+        in_memory: db.path().is_none(),
+        error: DB_ERROR.get().cloned(),
+    })
+}
+
+// Backup commands
+#[tauri::command]
+#[specta::specta]
+fn create_backup() -> AppResult<String> {
+    let path = get_db().backup("manual")?;
+    Ok(format!("Backup saved to: {}", path.display()))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn list_backups() -> AppResult<Vec<backup::BackupInfo>> {
+    Ok(get_db().list_backups())
+}
+
+#[tauri::command]
+#[specta::specta]
+fn restore_backup(file_name: String) -> AppResult<String> {
+    get_db().restore_backup(&file_name)?;
+    
+    // Cached calendar data came from the backup as well
+    invalidate_all_caches();
+    Ok(format!("Restored backup {}", file_name))
+}
+
+// Settings bundle commands
+#[tauri::command]
+#[specta::specta]
+fn export_settings_bundle(custom_path: Option<String>, include_secrets: Option<bool>) -> AppResult<String> {
+    let bundle = bundle::export(get_db(), include_secrets.unwrap_or(false))?;
+    let content = serde_json::to_string_pretty(&bundle)?;
+    
+    let filename = format!("tock-ui-settings_{}.json", chrono::Local::now().format("%Y-%m-%d"));
+    let filepath = write_export_file(custom_path, &filename, &content)?;
+    Ok(format!("Settings exported to: {}", filepath.display()))
+}
+
+#[tauri::command]
+#[specta::specta]
+fn import_settings_bundle(path: String, mode: Option<String>) -> AppResult<bundle::ImportReport> {
+    let mode = bundle::ImportMode::parse(mode.as_deref().unwrap_or("merge"))?;
+    
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| AppError::Io(format!("Failed to read {}: {}", path, e)))?;
+    let settings: bundle::SettingsBundle = serde_json::from_str(&content)
+        .map_err(|e| AppError::Validation(format!("Invalid settings bundle: {}", e)))?;
+    
+    bundle::import(get_db(), &settings, mode)
+}
+
+// Hit rate, evictions and memory use of the in-memory command cache
+#[tauri::command]
+#[specta::specta]
+fn get_cache_stats() -> AppResult<CacheStats> {
+    Ok(get_cache().stats())
 }
 
 // Calendar Cache commands
 #[tauri::command]
-fn get_calendar_cache(year_month: String) -> CommandResult {
-    match get_db().get_calendar_cache(&year_month) {
-        Ok(Some(cache)) => CommandResult {
-            success: true,
-            output: cache.data,
-            error: None,
-        },
-        Ok(None) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("No cache found".to_string()),
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get calendar cache: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_calendar_cache(year_month: String) -> AppResult<Option<String>> {
+    Ok(get_db().get_calendar_cache(&year_month)?.map(|cache| cache.data))
 }
 
 #[tauri::command]
-fn save_calendar_cache(year_month: String, data: String) -> CommandResult {
-    match get_db().save_calendar_cache(&year_month, &data) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Calendar cache saved".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to save calendar cache: {}", e)),
-        },
-    }
+#[specta::specta]
+fn save_calendar_cache(year_month: String, data: String) -> AppResult<()> {
+    Ok(get_db().save_calendar_cache(&year_month, &data)?)
 }
 
 #[tauri::command]
-fn clear_calendar_cache(year_month: String) -> CommandResult {
-    match get_db().clear_calendar_cache(&year_month) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Calendar cache cleared".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to clear calendar cache: {}", e)),
-        },
-    }
+#[specta::specta]
+fn clear_calendar_cache(year_month: String) -> AppResult<()> {
+    Ok(get_db().clear_calendar_cache(&year_month)?)
 }
 
 // Cached Projects commands
 #[tauri::command]
-fn get_cached_projects(api_route_id: Option<i64>) -> CommandResult {
-    match get_db().get_cached_projects(api_route_id) {
-        Ok(projects) => {
-            match serde_json::to_string(&projects) {
-                Ok(json) => CommandResult {
-                    success: true,
-                    output: json,
-                    error: None,
-                },
-                Err(e) => CommandResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to serialize cached projects: {}", e)),
-                },
-            }
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get cached projects: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_cached_projects(api_route_id: Option<i64>) -> AppResult<Vec<db::CachedProject>> {
+    Ok(get_db().get_cached_projects(api_route_id)?)
 }
 
 #[tauri::command]
-async fn sync_api_projects(api_route_id: i64) -> CommandResult {
+#[specta::specta]
+async fn sync_api_projects(api_route_id: i64) -> AppResult<String> {
     // Get the API route
-    let routes = match get_db().get_all_api_routes() {
-        Ok(r) => r,
-        Err(e) => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get API routes: {}", e)),
-        },
-    };
-    
-    let api_route = match routes.iter().find(|r| r.id == Some(api_route_id)) {
-        Some(route) => route,
-        None => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("API route not found".to_string()),
-        },
-    };
+    let routes = get_db().get_all_api_routes()?;
+    let api_route = routes
+        .iter()
+        .find(|r| r.id == Some(api_route_id))
+        .ok_or_else(|| AppError::NotFound("API route not found".to_string()))?;
     
     // Fetch projects from API
-    let fetch_result = fetch_projects_from_api(api_route.url.clone()).await;
-    
-    if !fetch_result.success {
-        return fetch_result;
-    }
+    let response = fetch_projects(&api_route.url, &api_route.auth).await?;
     
     // Parse the projects
-    let projects_data: serde_json::Value = match serde_json::from_str(&fetch_result.output) {
-        Ok(data) => data,
-        Err(e) => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to parse API response: {}", e)),
-        },
-    };
+    let projects_data: serde_json::Value = serde_json::from_str(&response)
+        .map_err(|e| AppError::Network(format!("Failed to parse API response: {}", e)))?;
     
     // Extract projects from response
-    let projects_array = if projects_data.is_array() {
-        projects_data.as_array().unwrap()
+    let projects_array = if let Some(projects) = projects_data.as_array() {
+        projects
     } else if let Some(results) = projects_data.get("results").and_then(|r| r.as_array()) {
         results
     } else {
-        return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some("Invalid API response format".to_string()),
-        };
+        return Err(AppError::Network("Invalid API response format".to_string()));
     };
     
     // Convert to tuples for database insertion
@@ -1425,30 +1553,14 @@ async fn sync_api_projects(api_route_id: i64) -> CommandResult {
         .collect();
     
     // Save to database
-    match get_db().save_cached_projects(&projects_to_save) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: format!("Synced {} projects from {}", projects_to_save.len(), api_route.name),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to save projects to database: {}", e)),
-        },
-    }
+    get_db().save_cached_projects(&projects_to_save)?;
+    Ok(format!("Synced {} projects from {}", projects_to_save.len(), api_route.name))
 }
 
 #[tauri::command]
-async fn sync_all_api_projects() -> CommandResult {
-    let routes = match get_db().get_all_api_routes() {
-        Ok(r) => r,
-        Err(e) => return CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get API routes: {}", e)),
-        },
-    };
+#[specta::specta]
+async fn sync_all_api_projects() -> AppResult<String> {
+    let routes = get_db().get_all_api_routes()?;
     
     let enabled_routes: Vec<_> = routes.into_iter().filter(|r| r.enabled).collect();
     let mut synced_count = 0;
@@ -1456,92 +1568,64 @@ async fn sync_all_api_projects() -> CommandResult {
     
     for route in enabled_routes {
         if let Some(id) = route.id {
-            let result = sync_api_projects(id).await;
-            if result.success {
-                synced_count += 1;
-            } else {
-                errors.push(format!("{}: {}", route.name, result.error.unwrap_or_default()));
+            match sync_api_projects(id).await {
+                Ok(_) => synced_count += 1,
+                Err(e) => errors.push(format!("{}: {}", route.name, e)),
             }
         }
     }
     
     if errors.is_empty() {
-        CommandResult {
-            success: true,
-            output: format!("Successfully synced {} API routes", synced_count),
-            error: None,
-        }
+        Ok(format!("Successfully synced {} API routes", synced_count))
+    } else if synced_count > 0 {
+        Ok(format!(
+            "Synced {} routes with {} errors: {}",
+            synced_count,
+            errors.len(),
+            errors.join("; ")
+        ))
     } else {
-        CommandResult {
-            success: synced_count > 0,
-            output: format!("Synced {} routes with {} errors", synced_count, errors.len()),
-            error: Some(errors.join("; ")),
-        }
+        Err(AppError::Network(errors.join("; ")))
     }
 }
 
 #[tauri::command]
-fn delete_cached_projects_by_api(api_route_id: i64) -> CommandResult {
-    match get_db().delete_cached_projects_by_api(api_route_id) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Cached projects deleted".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to delete cached projects: {}", e)),
-        },
-    }
+#[specta::specta]
+fn delete_cached_projects_by_api(api_route_id: i64) -> AppResult<()> {
+    Ok(get_db().delete_cached_projects_by_api(api_route_id)?)
 }
 
-// User Preferences commands
+// Settings commands
 #[tauri::command]
-fn get_user_preference(key: String) -> CommandResult {
-    match get_db().get_preference(&key) {
-        Ok(Some(value)) => CommandResult {
-            success: true,
-            output: value,
-            error: None,
-        },
-        Ok(None) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to get preference: {}", e)),
-        },
-    }
+#[specta::specta]
+fn get_settings() -> AppResult<Settings> {
+    Ok(Settings::load(get_db()))
 }
 
 #[tauri::command]
-fn set_user_preference(key: String, value: String) -> CommandResult {
-    match get_db().set_preference(&key, &value) {
-        Ok(_) => CommandResult {
-            success: true,
-            output: "Preference saved".to_string(),
-            error: None,
-        },
-        Err(e) => CommandResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("Failed to save preference: {}", e)),
-        },
+#[specta::specta]
+fn update_settings(settings: Settings) -> AppResult<Settings> {
+    let previous = Settings::load(get_db());
+    settings.save(get_db())?;
+    
+    // Cached activity data belongs to the previous backend or tock setup
+    if previous.tracker_backend != settings.tracker_backend
+        || previous.tock_file_path != settings.tock_file_path
+        || previous.tock_binary_path != settings.tock_binary_path
+        || previous.tock_env != settings.tock_env
+    {
+        invalidate_all_caches();
     }
+    
+    Ok(settings)
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_dialog::init())
-        .plugin(tauri_plugin_updater::Builder::new().build())
-        .plugin(tauri_plugin_process::init())
-        .invoke_handler(tauri::generate_handler![
+// Commands exposed to the frontend. Their signatures, every type they use
+// and event payloads are also exported as TypeScript to src/bindings.ts.
+fn specta_builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new()
+        .error_handling(ErrorHandlingMode::Throw)
+        .commands(tauri_specta::collect_commands![
             start_activity,
             stop_activity,
             add_activity,
@@ -1549,10 +1633,19 @@ pub fn run() {
             get_current_activity,
             get_recent_activities,
             get_report,
+            get_available_backends,
+            get_current_activity_structured,
+            get_recent_activities_structured,
+            get_report_structured,
             check_tock_installed,
+            detect_tock_path,
+            get_tock_capabilities,
             auto_install_tock,
             get_activities_for_date,
             get_activities_for_month,
+            get_activities_for_month_structured,
+            cancel_operation,
+            get_range_report,
             save_report_to_file,
             add_favorite,
             remove_favorite,
@@ -1570,7 +1663,22 @@ pub fn run() {
             get_report_settings,
             update_report_settings,
             send_monthly_report_to_api,
+            update_report_period_settings,
+            update_report_schedule,
+            get_next_report_due,
             check_and_send_auto_report,
+            get_outbox,
+            retry_outbox_entry,
+            discard_outbox_entry,
+            get_report_delivery_history,
+            get_scheduler_jobs,
+            get_database_info,
+            create_backup,
+            list_backups,
+            restore_backup,
+            export_settings_bundle,
+            import_settings_bundle,
+            get_cache_stats,
             get_calendar_cache,
             save_calendar_cache,
             clear_calendar_cache,
@@ -1578,9 +1686,53 @@ pub fn run() {
             sync_api_projects,
             sync_all_api_projects,
             delete_cached_projects_by_api,
-            get_user_preference,
-            set_user_preference
+            get_settings,
+            update_settings
         ])
+        // Event payloads
+        .typ::<watcher::TockDataChanged>()
+        .typ::<MonthLoadProgress>()
+}
+
+// Where debug builds write the generated bindings, relative to src-tauri
+pub const BINDINGS_PATH: &str = "../src/bindings.ts";
+
+// TypeScript types and invoke wrappers for every command
+pub fn typescript_bindings() -> Result<String, specta_typescript::ExportError> {
+    specta_builder().export_str(bindings_language())
+}
+
+fn bindings_language() -> Typescript {
+    Typescript::default()
+        // The generated helpers are not all used, which strict tsc rejects
+        .header("// @ts-nocheck\n// Regenerate with `UPDATE_BINDINGS=1 cargo test --test bindings` in src-tauri.")
+        // Ids, counts and durations all fit in a JS number
+        .bigint(BigIntExportBehavior::Number)
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    let builder = specta_builder();
+
+    #[cfg(debug_assertions)]
+    if let Err(e) = builder.export(bindings_language(), BINDINGS_PATH) {
+        eprintln!("Warning: Failed to export TypeScript bindings: {}", e);
+    }
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin(tauri_plugin_process::init())
+        .setup(|app| {
+            // Auto-send, project sync and cache warming run in the background
+            scheduler::start(app.handle().clone());
+            // Pick up changes made by running tock outside the app
+            watcher::start(app.handle().clone());
+            command_cache::start_eviction(get_cache());
+            Ok(())
+        })
+        .invoke_handler(builder.invoke_handler())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
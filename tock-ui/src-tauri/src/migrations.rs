@@ -0,0 +1,330 @@
+use rusqlite::{Connection, Transaction, Result as SqlResult};
+
+// Versioned schema migrations for tock_ui.db.
+//
+// The schema version is stored in `PRAGMA user_version`. On startup every
+// migration newer than that version runs in order, each in its own
+// transaction together with the version bump, so a failed migration leaves
+// the database at the previous version.
+//
+// Never edit a migration that has shipped; add a new one to the end instead.
+
+struct Migration {
+    version: i64,
+    description: &'static str,
+    up: fn(&Transaction) -> SqlResult<()>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, description: "initial schema", up: initial_schema },
+    Migration { version: 2, description: "api route authentication", up: api_route_auth },
+    Migration { version: 3, description: "report outbox", up: report_outbox },
+    Migration { version: 4, description: "report delivery history", up: report_deliveries },
+    Migration { version: 5, description: "report periods", up: report_periods },
+    Migration { version: 6, description: "report schedules", up: report_schedules },
+    Migration { version: 7, description: "scheduler jobs", up: scheduler_jobs },
+];
+
+// Version of the newest migration
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+pub fn current_version(conn: &Connection) -> SqlResult<i64> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+// Bring the database up to the latest version. Returns the versions applied.
+pub fn run(conn: &mut Connection) -> SqlResult<Vec<i64>> {
+    let current = current_version(conn)?;
+    if current > latest_version() {
+        eprintln!(
+            "Warning: Database schema version {} is newer than this build supports ({})",
+            current,
+            latest_version()
+        );
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        (migration.up)(&tx)?;
+        tx.pragma_update(None, "user_version", migration.version)?;
+        tx.commit()?;
+
+        eprintln!("Applied database migration {}: {}", migration.version, migration.description);
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+// Add a column unless it already exists
+fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> SqlResult<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<SqlResult<Vec<_>>>()?
+        .iter()
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+// Tables as they existed before migrations were introduced. Databases from
+// those releases already have them, hence IF NOT EXISTS.
+fn initial_schema(tx: &Transaction) -> SqlResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS favorite_projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE(name, description)
+        );
+
+        CREATE TABLE IF NOT EXISTS api_routes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            url TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS report_api_routes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS report_settings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            auto_send_enabled INTEGER NOT NULL DEFAULT 0,
+            selected_api_route_id INTEGER,
+            last_sent_at TEXT,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(selected_api_route_id) REFERENCES report_api_routes(id) ON DELETE SET NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS cached_projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            source_api_route_id INTEGER,
+            last_synced TEXT NOT NULL,
+            UNIQUE(name, description, source_api_route_id),
+            FOREIGN KEY(source_api_route_id) REFERENCES api_routes(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS calendar_cache (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            year_month TEXT NOT NULL UNIQUE,
+            data TEXT NOT NULL,
+            cached_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS user_preferences (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL UNIQUE,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );",
+    )
+}
+
+fn api_route_auth(tx: &Transaction) -> SqlResult<()> {
+    add_column(tx, "api_routes", "auth_config", "TEXT")?;
+    add_column(tx, "report_api_routes", "auth_config", "TEXT")
+}
+
+fn report_outbox(tx: &Transaction) -> SqlResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS outbox (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            api_route_id INTEGER NOT NULL,
+            period TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            attempts INTEGER NOT NULL DEFAULT 0,
+            next_attempt_at TEXT,
+            last_error TEXT,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY(api_route_id) REFERENCES report_api_routes(id) ON DELETE CASCADE
+        );",
+    )
+}
+
+fn report_deliveries(tx: &Transaction) -> SqlResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS report_deliveries (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            attempted_at TEXT NOT NULL,
+            api_route_id INTEGER NOT NULL,
+            route_name TEXT NOT NULL,
+            route_url TEXT NOT NULL,
+            period TEXT NOT NULL,
+            payload_hash TEXT NOT NULL,
+            success INTEGER NOT NULL,
+            status_code INTEGER,
+            response_body TEXT,
+            error TEXT
+        );",
+    )
+}
+
+fn report_periods(tx: &Transaction) -> SqlResult<()> {
+    add_column(tx, "report_settings", "period_type", "TEXT NOT NULL DEFAULT 'month'")?;
+    add_column(tx, "report_settings", "cutoff_day", "INTEGER")?;
+    add_column(tx, "report_settings", "send_partial", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column(tx, "report_settings", "last_closed_period", "TEXT")?;
+    add_column(tx, "outbox", "partial", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column(tx, "report_deliveries", "partial", "INTEGER NOT NULL DEFAULT 0")
+}
+
+fn report_schedules(tx: &Transaction) -> SqlResult<()> {
+    add_column(tx, "report_settings", "schedule_config", "TEXT")?;
+    add_column(tx, "report_settings", "last_auto_run_at", "TEXT")
+}
+
+fn scheduler_jobs(tx: &Transaction) -> SqlResult<()> {
+    tx.execute_batch(
+        "CREATE TABLE IF NOT EXISTS scheduler_jobs (
+            name TEXT PRIMARY KEY,
+            last_run_at TEXT,
+            next_run_at TEXT,
+            last_success INTEGER,
+            last_message TEXT
+        );",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Schema created by releases before migrations, which only had the
+    // original tables
+    const UNVERSIONED_SCHEMA: &str = "
+        CREATE TABLE favorite_projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            UNIQUE(name, description)
+        );
+        CREATE TABLE api_routes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE,
+            url TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+        CREATE TABLE report_api_routes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL
+        );
+        CREATE TABLE report_settings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            auto_send_enabled INTEGER NOT NULL DEFAULT 0,
+            selected_api_route_id INTEGER,
+            last_sent_at TEXT,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY(selected_api_route_id) REFERENCES report_api_routes(id) ON DELETE SET NULL
+        );
+        CREATE TABLE cached_projects (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            source_api_route_id INTEGER,
+            last_synced TEXT NOT NULL,
+            UNIQUE(name, description, source_api_route_id),
+            FOREIGN KEY(source_api_route_id) REFERENCES api_routes(id) ON DELETE CASCADE
+        );
+        CREATE TABLE calendar_cache (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            year_month TEXT NOT NULL UNIQUE,
+            data TEXT NOT NULL,
+            cached_at TEXT NOT NULL
+        );
+        CREATE TABLE user_preferences (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL UNIQUE,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );";
+
+    const SAMPLE_DATA: &str = "
+        INSERT INTO favorite_projects (name, description, created_at) VALUES ('Acme', 'Support', '2026-01-01');
+        INSERT INTO api_routes (name, url, created_at) VALUES ('Projects', 'https://example.com/projects', '2026-01-01');
+        INSERT INTO report_api_routes (name, url, created_at) VALUES ('Reports', 'https://example.com/reports', '2026-01-01');
+        INSERT INTO report_settings (auto_send_enabled, selected_api_route_id, last_sent_at, updated_at)
+            VALUES (1, 1, '2026-02-01', '2026-01-01');
+        INSERT INTO user_preferences (key, value, updated_at) VALUES ('theme', '\"dark\"', '2026-01-01');";
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+        let names = stmt.query_map([], |row| row.get(1)).unwrap();
+        names.collect::<SqlResult<Vec<String>>>().unwrap()
+    }
+
+    #[test]
+    fn upgrades_database_without_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(UNVERSIONED_SCHEMA).unwrap();
+        conn.execute_batch(SAMPLE_DATA).unwrap();
+
+        let applied = run(&mut conn).unwrap();
+        assert_eq!(applied, (1..=latest_version()).collect::<Vec<_>>());
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+
+        // Added columns and tables
+        assert!(columns(&conn, "api_routes").contains(&"auth_config".to_string()));
+        assert!(columns(&conn, "report_api_routes").contains(&"auth_config".to_string()));
+        let report_settings = columns(&conn, "report_settings");
+        for column in ["period_type", "cutoff_day", "send_partial", "last_closed_period", "schedule_config", "last_auto_run_at"] {
+            assert!(report_settings.contains(&column.to_string()), "missing report_settings.{}", column);
+        }
+        assert!(columns(&conn, "outbox").contains(&"partial".to_string()));
+        assert!(columns(&conn, "report_deliveries").contains(&"partial".to_string()));
+        assert!(!columns(&conn, "scheduler_jobs").is_empty());
+
+        // Existing rows are intact and get the column defaults
+        let favorite: (String, String) = conn
+            .query_row("SELECT name, description FROM favorite_projects", [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(favorite, ("Acme".to_string(), "Support".to_string()));
+        let settings: (bool, i64, String, String) = conn
+            .query_row(
+                "SELECT auto_send_enabled, selected_api_route_id, last_sent_at, period_type FROM report_settings",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .unwrap();
+        assert_eq!(settings, (true, 1, "2026-02-01".to_string(), "month".to_string()));
+        let url: String = conn.query_row("SELECT url FROM report_api_routes", [], |row| row.get(0)).unwrap();
+        assert_eq!(url, "https://example.com/reports");
+        let theme: String = conn
+            .query_row("SELECT value FROM user_preferences WHERE key = 'theme'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(theme, "\"dark\"");
+
+        // Nothing left to apply
+        assert!(run(&mut conn).unwrap().is_empty());
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+    }
+
+    #[test]
+    fn new_database_gets_latest_schema() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run(&mut conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert!(run(&mut conn).unwrap().is_empty());
+    }
+}
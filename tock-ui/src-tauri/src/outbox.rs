@@ -0,0 +1,55 @@
+use chrono::{DateTime, Duration, Utc};
+
+// Retry policy for report deliveries queued in the outbox table.
+//
+// A failed delivery is retried after 1 minute, then 2, 4, 8... minutes,
+// capped at 6 hours between attempts. After MAX_AUTOMATIC_ATTEMPTS failures
+// the entry stays in the outbox until it is retried or discarded by hand.
+
+pub const MAX_AUTOMATIC_ATTEMPTS: i64 = 12;
+
+const BASE_DELAY_SECONDS: i64 = 60;
+const MAX_DELAY_SECONDS: i64 = 6 * 60 * 60;
+
+// Delay before the next attempt after `attempts` failed attempts
+pub fn backoff_delay(attempts: i64) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 30) as u32;
+    let seconds = BASE_DELAY_SECONDS.saturating_mul(1_i64 << exponent);
+    Duration::seconds(seconds.min(MAX_DELAY_SECONDS))
+}
+
+// When to retry after `attempts` failed attempts, as a UTC RFC 3339 string.
+// None means automatic retries are exhausted.
+pub fn next_attempt_at(attempts: i64, now: DateTime<Utc>) -> Option<String> {
+    if attempts >= MAX_AUTOMATIC_ATTEMPTS {
+        return None;
+    }
+    Some((now + backoff_delay(attempts)).to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_delay_up_to_six_hours() {
+        let minutes = |attempts| backoff_delay(attempts).num_minutes();
+        assert_eq!(minutes(0), 1);
+        assert_eq!(minutes(1), 1);
+        assert_eq!(minutes(2), 2);
+        assert_eq!(minutes(3), 4);
+        assert_eq!(minutes(9), 256);
+        assert_eq!(minutes(10), 6 * 60);
+        assert_eq!(minutes(11), 6 * 60);
+        assert_eq!(minutes(1000), 6 * 60);
+    }
+
+    #[test]
+    fn stops_retrying_after_max_attempts() {
+        let now = DateTime::parse_from_rfc3339("2026-09-30T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(next_attempt_at(1, now).as_deref(), Some("2026-09-30T12:01:00+00:00"));
+        assert_eq!(next_attempt_at(11, now).as_deref(), Some("2026-09-30T18:00:00+00:00"));
+        assert_eq!(next_attempt_at(MAX_AUTOMATIC_ATTEMPTS, now), None);
+        assert_eq!(next_attempt_at(MAX_AUTOMATIC_ATTEMPTS + 1, now), None);
+    }
+}
@@ -0,0 +1,356 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+use crate::Activity;
+
+// Parsers for the text printed by the tock CLI.
+//
+// `tock last` and `tock current` print a table:
+//   Start             Description   Project   Duration
+//   2026-01-01 13:46  write docs    tock-ui   1h3m0s
+//
+// `tock report` prints activities grouped by project:
+//   📁 tock-ui: 1h 3m
+//      13:46 - 14:49 (1h 3m) | write docs
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+const DATETIME_LEN: usize = 16; // "YYYY-MM-DD HH:MM"
+
+// Parse the table printed by `tock last`. End times are derived from the start
+// time plus the printed duration.
+pub fn parse_activity_table(output: &str) -> Vec<Activity> {
+    output
+        .lines()
+        .filter_map(parse_table_line)
+        .collect()
+}
+
+// Parse the table printed by `tock current`. The duration of a running
+// activity is the elapsed time so far, so no end time is set.
+pub fn parse_current(output: &str) -> Vec<Activity> {
+    if output.contains("No current activity") {
+        return Vec::new();
+    }
+
+    parse_activity_table(output)
+        .into_iter()
+        .map(|mut activity| {
+            activity.end_time = None;
+            activity
+        })
+        .collect()
+}
+
+// Parse the output of `tock report` for a single day. `date` is used to build
+// full timestamps since report lines only carry the time of day.
+pub fn parse_report(output: &str, date: NaiveDate) -> Vec<Activity> {
+    let mut activities = Vec::new();
+    let mut current_project: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.contains("Time Tracking Report") || line.starts_with("===") {
+            continue;
+        }
+
+        // Project header: "📁 tock-ui: 1h 3m"
+        if let Some(header) = line.strip_prefix("📁") {
+            current_project = header
+                .rsplit_once(':')
+                .map(|(name, _)| name.trim().to_string())
+                .filter(|name| !name.is_empty());
+            continue;
+        }
+
+        if let Some(project) = &current_project {
+            if let Some(activity) = parse_report_line(line, project, date) {
+                activities.push(activity);
+            }
+        }
+    }
+
+    activities
+}
+
+// Parse the combined output of `get_activities_for_month`, where each day's
+// report is preceded by a "=== YYYY-MM-DD ===" separator.
+pub fn parse_month_report(output: &str) -> Vec<Activity> {
+    let mut activities = Vec::new();
+    let mut current_date: Option<NaiveDate> = None;
+    let mut section = String::new();
+
+    for line in output.lines() {
+        if let Some(date) = parse_date_separator(line) {
+            if let Some(d) = current_date {
+                activities.extend(parse_report(&section, d));
+            }
+            current_date = Some(date);
+            section.clear();
+        } else {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+
+    if let Some(d) = current_date {
+        activities.extend(parse_report(&section, d));
+    }
+
+    activities
+}
+
+fn parse_date_separator(line: &str) -> Option<NaiveDate> {
+    let inner = line
+        .trim()
+        .strip_prefix("===")?
+        .strip_suffix("===")?
+        .trim();
+    NaiveDate::parse_from_str(inner, "%Y-%m-%d").ok()
+}
+
+fn parse_table_line(line: &str) -> Option<Activity> {
+    let line = line.trim();
+    let start_str = line.get(..DATETIME_LEN)?;
+    let start = NaiveDateTime::parse_from_str(start_str, DATETIME_FORMAT).ok()?;
+    let rest = line[DATETIME_LEN..].trim();
+
+    // Columns are padded with at least two spaces, which lets descriptions
+    // contain single spaces. Fall back to whitespace splitting otherwise.
+    let columns: Vec<&str> = rest
+        .split("  ")
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .collect();
+
+    let (description, project, duration_str) = if columns.len() == 3 {
+        (columns[0].to_string(), columns[1].to_string(), columns[2])
+    } else {
+        let mut tokens: Vec<&str> = rest.split_whitespace().collect();
+        if tokens.len() < 3 {
+            return None;
+        }
+        let duration_str = tokens.pop()?;
+        let project = tokens.pop()?;
+        (tokens.join(" "), project.to_string(), duration_str)
+    };
+
+    let duration = parse_duration(duration_str);
+
+    Some(Activity {
+        project,
+        description,
+        start_time: Some(start),
+        end_time: duration.map(|d| start + d),
+        duration_seconds: duration.map(|d| d.num_seconds()),
+    })
+}
+
+// Parse a report activity line: "13:46 - 14:49 (1h 3m) | write docs".
+// The start may also carry a date ("2026-01-01 13:46 - 14:49 ...").
+fn parse_report_line(line: &str, project: &str, date: NaiveDate) -> Option<Activity> {
+    let (times, description) = match line.split_once('|') {
+        Some((times, description)) => (times.trim(), description.trim()),
+        None => (line, ""),
+    };
+
+    let (range, duration_str) = match times.split_once('(') {
+        Some((range, rest)) => (range.trim(), rest.trim_end_matches(')').trim()),
+        None => (times, ""),
+    };
+
+    let (start_str, end_str) = range.split_once(" - ")?;
+    let start = parse_report_time(start_str.trim(), date)?;
+    let mut end = parse_report_time(end_str.trim(), start.date());
+
+    // An end time earlier than the start means the activity crossed midnight
+    if let Some(e) = end {
+        if e < start {
+            end = Some(e + Duration::days(1));
+        }
+    }
+
+    let duration = parse_duration(duration_str).or_else(|| end.map(|e| e - start));
+
+    Some(Activity {
+        project: project.to_string(),
+        description: description.to_string(),
+        start_time: Some(start),
+        end_time: end,
+        duration_seconds: duration.map(|d| d.num_seconds()),
+    })
+}
+
+fn parse_report_time(value: &str, date: NaiveDate) -> Option<NaiveDateTime> {
+    if let Ok(dt) = NaiveDateTime::parse_from_str(value, DATETIME_FORMAT) {
+        return Some(dt);
+    }
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .ok()
+        .map(|t| date.and_time(t))
+}
+
+// Parse durations printed by tock, e.g. "1h3m0s", "45s", "1h 3m" or "0h 1m".
+// Go prints durations under a second as "500ms", "1.5µs" and so on.
+pub fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    let mut total_seconds: f64 = 0.0;
+    let mut number = String::new();
+    let mut unit = String::new();
+    let mut seen_unit = false;
+
+    // A trailing space flushes the last amount
+    for c in value.chars().chain(std::iter::once(' ')) {
+        let starts_amount = c.is_ascii_digit() || c == '.';
+        if !unit.is_empty() && (starts_amount || c.is_whitespace()) {
+            let amount: f64 = number.parse().ok()?;
+            total_seconds += amount * unit_seconds(&unit)?;
+            number.clear();
+            unit.clear();
+            seen_unit = true;
+        }
+
+        if starts_amount {
+            number.push(c);
+        } else if !c.is_whitespace() {
+            unit.push(c);
+        }
+    }
+
+    if !number.is_empty() || !seen_unit {
+        return None;
+    }
+
+    Some(Duration::milliseconds((total_seconds * 1000.0).round() as i64))
+}
+
+fn unit_seconds(unit: &str) -> Option<f64> {
+    match unit {
+        "h" => Some(3600.0),
+        "m" => Some(60.0),
+        "s" => Some(1.0),
+        "ms" => Some(1e-3),
+        "us" | "µs" | "μs" => Some(1e-6),
+        "ns" => Some(1e-9),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, DATETIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn activity_table_with_padded_columns() {
+        let output = "Start             Description          Project   Duration\n\
+                      2026-01-01 13:46  write the docs       tock-ui   1h3m0s\n\
+                      2026-01-01 15:00  review               tock-ui   45s\n";
+        let activities = parse_activity_table(output);
+
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].description, "write the docs");
+        assert_eq!(activities[0].project, "tock-ui");
+        assert_eq!(activities[0].start_time, Some(datetime("2026-01-01 13:46")));
+        assert_eq!(activities[0].end_time, Some(datetime("2026-01-01 14:49")));
+        assert_eq!(activities[0].duration_seconds, Some(3780));
+        assert_eq!(activities[1].duration_seconds, Some(45));
+    }
+
+    #[test]
+    fn activity_table_with_single_spaces() {
+        // Without two-space padding the last two words are project and duration
+        let activities = parse_activity_table("2026-01-01 13:46 write the docs tock-ui 1h3m0s");
+
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].description, "write the docs");
+        assert_eq!(activities[0].project, "tock-ui");
+        assert_eq!(activities[0].duration_seconds, Some(3780));
+    }
+
+    #[test]
+    fn current_without_activity() {
+        assert!(parse_current("No current activity\n").is_empty());
+    }
+
+    #[test]
+    fn current_has_no_end_time() {
+        let activities = parse_current("2026-01-01 13:46  write docs  tock-ui  10m0s");
+
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].end_time, None);
+        assert_eq!(activities[0].duration_seconds, Some(600));
+    }
+
+    #[test]
+    fn report_with_colon_in_project_name() {
+        let output = "📊 Time Tracking Report\n\
+                      📁 client: acme: 1h 3m\n\
+                      \u{20}  13:46 - 14:49 (1h 3m) | write docs\n";
+        let activities = parse_report(output, date("2026-01-01"));
+
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].project, "client: acme");
+        assert_eq!(activities[0].description, "write docs");
+        assert_eq!(activities[0].end_time, Some(datetime("2026-01-01 14:49")));
+    }
+
+    #[test]
+    fn report_crossing_midnight() {
+        let output = "📁 tock-ui: 1h 30m\n   23:30 - 01:00 (1h 30m) | release\n";
+        let activities = parse_report(output, date("2026-01-01"));
+
+        assert_eq!(activities[0].start_time, Some(datetime("2026-01-01 23:30")));
+        assert_eq!(activities[0].end_time, Some(datetime("2026-01-02 01:00")));
+        assert_eq!(activities[0].duration_seconds, Some(5400));
+    }
+
+    #[test]
+    fn report_with_running_activity() {
+        let output = "📁 tock-ui: 0h 20m\n   13:00 - now (0h 20m) | write tests\n";
+        let activities = parse_report(output, date("2026-01-01"));
+
+        assert_eq!(activities.len(), 1);
+        assert_eq!(activities[0].start_time, Some(datetime("2026-01-01 13:00")));
+        assert_eq!(activities[0].end_time, None);
+        assert_eq!(activities[0].duration_seconds, Some(1200));
+    }
+
+    #[test]
+    fn month_report_uses_separator_dates() {
+        let output = "=== 2026-01-01 ===\n\
+                      📁 tock-ui: 1h 0m\n   09:00 - 10:00 (1h 0m) | first\n\n\
+                      === 2026-01-02 ===\n\
+                      📁 docs: 0h 30m\n   11:00 - 11:30 (0h 30m) | second\n";
+        let activities = parse_month_report(output);
+
+        assert_eq!(activities.len(), 2);
+        assert_eq!(activities[0].start_time, Some(datetime("2026-01-01 09:00")));
+        assert_eq!(activities[0].project, "tock-ui");
+        assert_eq!(activities[1].start_time, Some(datetime("2026-01-02 11:00")));
+        assert_eq!(activities[1].project, "docs");
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("1h3m0s"), Some(Duration::seconds(3780)));
+        assert_eq!(parse_duration("1h 3m"), Some(Duration::seconds(3780)));
+        assert_eq!(parse_duration("45s"), Some(Duration::seconds(45)));
+        assert_eq!(parse_duration("500ms"), Some(Duration::milliseconds(500)));
+        assert_eq!(parse_duration("1m0.5s"), Some(Duration::milliseconds(60_500)));
+        assert_eq!(parse_duration("1.5µs"), Some(Duration::zero()));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("12"), None);
+        assert_eq!(parse_duration("1x"), None);
+    }
+}
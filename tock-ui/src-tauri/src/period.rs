@@ -0,0 +1,185 @@
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+
+// Reporting periods used when sending reports to a report API route.
+//
+// Automatic sending submits each closed period exactly once; the period that
+// is still in progress can optionally be sent as a partial update.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PeriodKind {
+    Month,
+    IsoWeek,
+    // Month-long period ending on the given day of each month (1-28),
+    // e.g. 25 covers the 26th of one month to the 25th of the next
+    Cutoff(u32),
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ReportPeriod {
+    // Stable identifier, e.g. "2026-09", "2026-W39" or "2026-08-26..2026-09-25"
+    pub key: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl PeriodKind {
+    // Build from the `period_type` and `cutoff_day` report settings
+    pub fn from_settings(period_type: &str, cutoff_day: Option<u32>) -> Result<Self, String> {
+        match period_type {
+            "month" => Ok(PeriodKind::Month),
+            "iso_week" => Ok(PeriodKind::IsoWeek),
+            "cutoff" => match cutoff_day {
+                Some(day) if (1..=28).contains(&day) => Ok(PeriodKind::Cutoff(day)),
+                Some(day) => Err(format!("Invalid cutoff day: {}. Use a day between 1 and 28.", day)),
+                None => Err("A cutoff day is required for cutoff periods".to_string()),
+            },
+            other => Err(format!("Unknown report period: {}. Use month, iso_week or cutoff.", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PeriodKind::Month => "month",
+            PeriodKind::IsoWeek => "iso_week",
+            PeriodKind::Cutoff(_) => "cutoff",
+        }
+    }
+
+    // The period that contains `date`
+    pub fn containing(&self, date: NaiveDate) -> ReportPeriod {
+        match *self {
+            PeriodKind::Month => {
+                let start = first_of_month(date.year(), date.month());
+                let end = add_month(start) - Duration::days(1);
+                ReportPeriod {
+                    key: start.format("%Y-%m").to_string(),
+                    start,
+                    end,
+                }
+            }
+            PeriodKind::IsoWeek => {
+                let start = date - Duration::days(date.weekday().num_days_from_monday() as i64);
+                let week = date.iso_week();
+                ReportPeriod {
+                    key: format!("{}-W{:02}", week.year(), week.week()),
+                    start,
+                    end: start + Duration::days(6),
+                }
+            }
+            PeriodKind::Cutoff(day) => {
+                let this_month = first_of_month(date.year(), date.month());
+                let end_month = if date.day() <= day { this_month } else { add_month(this_month) };
+                let end = end_month + Duration::days(day as i64 - 1);
+                let start = sub_month(end_month) + Duration::days(day as i64);
+                ReportPeriod {
+                    key: format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d")),
+                    start,
+                    end,
+                }
+            }
+        }
+    }
+
+    // The most recent period that ended before `today`
+    pub fn last_closed(&self, today: NaiveDate) -> ReportPeriod {
+        let current = self.containing(today);
+        self.containing(current.start - Duration::days(1))
+    }
+}
+
+fn first_of_month(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, 1).expect("first day of month is always valid")
+}
+
+// First day of the month after `first`
+fn add_month(first: NaiveDate) -> NaiveDate {
+    if first.month() == 12 {
+        first_of_month(first.year() + 1, 1)
+    } else {
+        first_of_month(first.year(), first.month() + 1)
+    }
+}
+
+// First day of the month before `first`
+fn sub_month(first: NaiveDate) -> NaiveDate {
+    if first.month() == 1 {
+        first_of_month(first.year() - 1, 12)
+    } else {
+        first_of_month(first.year(), first.month() - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    // "key first..last"
+    fn span(period: ReportPeriod) -> String {
+        format!("{} {}..{}", period.key, period.start, period.end)
+    }
+
+    #[test]
+    fn months() {
+        let month = PeriodKind::Month;
+        assert_eq!(span(month.containing(date("2028-02-29"))), "2028-02 2028-02-01..2028-02-29");
+        assert_eq!(span(month.containing(date("2026-12-31"))), "2026-12 2026-12-01..2026-12-31");
+        // On the first day of a month the previous month is the last closed one
+        assert_eq!(span(month.last_closed(date("2027-01-01"))), "2026-12 2026-12-01..2026-12-31");
+        assert_eq!(span(month.last_closed(date("2026-12-31"))), "2026-11 2026-11-01..2026-11-30");
+    }
+
+    #[test]
+    fn cutoff_periods() {
+        // Cutoff keys are the period's first and last day
+        let cutoff = |day, on| PeriodKind::Cutoff(day).containing(date(on)).key;
+        let last_closed = |day, on| PeriodKind::Cutoff(day).last_closed(date(on)).key;
+        assert_eq!(
+            span(PeriodKind::Cutoff(25).containing(date("2026-09-25"))),
+            "2026-08-26..2026-09-25 2026-08-26..2026-09-25"
+        );
+
+        // The cutoff day closes the period
+        assert_eq!(cutoff(25, "2026-09-26"), "2026-09-26..2026-10-25");
+        // Across December and January
+        assert_eq!(cutoff(25, "2026-12-26"), "2026-12-26..2027-01-25");
+        assert_eq!(cutoff(25, "2027-01-10"), "2026-12-26..2027-01-25");
+        assert_eq!(last_closed(25, "2026-12-26"), "2026-11-26..2026-12-25");
+        assert_eq!(last_closed(25, "2026-12-25"), "2026-10-26..2026-11-25");
+        assert_eq!(cutoff(1, "2027-01-01"), "2026-12-02..2027-01-01");
+        assert_eq!(cutoff(1, "2027-01-02"), "2027-01-02..2027-02-01");
+
+        // The 28th works in February
+        assert_eq!(cutoff(28, "2027-02-28"), "2027-01-29..2027-02-28");
+        assert_eq!(cutoff(28, "2027-03-01"), "2027-03-01..2027-03-28");
+        assert_eq!(last_closed(28, "2027-03-01"), "2027-01-29..2027-02-28");
+    }
+
+    #[test]
+    fn iso_weeks() {
+        let week = PeriodKind::IsoWeek;
+        assert_eq!(span(week.containing(date("2025-12-28"))), "2025-W52 2025-12-22..2025-12-28");
+        // 2026 has 53 weeks, the last one ends in 2027
+        assert_eq!(span(week.containing(date("2026-12-31"))), "2026-W53 2026-12-28..2027-01-03");
+        assert_eq!(span(week.containing(date("2027-01-03"))), "2026-W53 2026-12-28..2027-01-03");
+        assert_eq!(span(week.containing(date("2027-01-04"))), "2027-W01 2027-01-04..2027-01-10");
+        // Week 1 of 2025 starts in 2024
+        assert_eq!(span(week.containing(date("2024-12-30"))), "2025-W01 2024-12-30..2025-01-05");
+        // On a Monday the previous week is the last closed one
+        assert_eq!(span(week.last_closed(date("2027-01-04"))), "2026-W53 2026-12-28..2027-01-03");
+        assert_eq!(span(week.last_closed(date("2027-01-03"))), "2026-W52 2026-12-21..2026-12-27");
+    }
+
+    #[test]
+    fn reads_settings() {
+        assert_eq!(PeriodKind::from_settings("cutoff", Some(28)), Ok(PeriodKind::Cutoff(28)));
+        assert!(PeriodKind::from_settings("cutoff", Some(29)).is_err());
+        assert!(PeriodKind::from_settings("cutoff", None).is_err());
+        assert!(PeriodKind::from_settings("year", None).is_err());
+        assert_eq!(PeriodKind::from_settings("iso_week", None).map(|k| k.as_str()), Ok("iso_week"));
+    }
+}
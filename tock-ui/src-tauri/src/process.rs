@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::error::AppError;
+
+// Running external processes (tock, go, brew) without letting a hung one
+// block the calling command forever. Every run has a timeout after which the
+// child is killed, and can be cancelled through a CancelToken, e.g. when the
+// frontend navigates away from a month that is still loading. `run` blocks
+// the calling thread, `run_async` is for async commands.
+
+// Timeout for tock commands when settings don't say otherwise
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Installing tock downloads and builds it, which takes a while
+pub const INSTALL_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+// `--version` style checks should answer right away
+pub const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How often a running child is checked for exit, timeout and cancellation
+const POLL: Duration = Duration::from_millis(10);
+
+// Why a child was stopped before it finished
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Interruption {
+    TimedOut(Duration),
+    Cancelled,
+}
+
+impl Interruption {
+    // Error for a run of `program` that was stopped
+    pub fn into_error(self, program: &str) -> AppError {
+        match self {
+            Interruption::TimedOut(timeout) => AppError::Timeout(format!(
+                "{} did not finish within {} seconds and was stopped",
+                program,
+                timeout.as_secs()
+            )),
+            Interruption::Cancelled => AppError::Cancelled,
+        }
+    }
+}
+
+pub enum RunError {
+    // The process could not be started or waited on
+    Io(std::io::Error),
+    Interrupted(Interruption),
+}
+
+// Shared flag telling runs that their result is no longer wanted. Clones
+// share the flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    // Resolves once the token is cancelled
+    pub async fn cancelled(&self) {
+        while !self.is_cancelled() {
+            tokio::time::sleep(POLL).await;
+        }
+    }
+}
+
+// How long a run may take and what cancels it
+#[derive(Debug, Clone)]
+pub struct Limits {
+    pub timeout: Duration,
+    pub cancel: CancelToken,
+}
+
+impl Limits {
+    // Limits that only time out
+    pub fn timeout(timeout: Duration) -> Self {
+        Limits {
+            timeout,
+            cancel: CancelToken::default(),
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits::timeout(DEFAULT_TIMEOUT)
+    }
+}
+
+// Run `command` to completion like Command::output(), but kill it once the
+// timeout passes or the token is cancelled. Stdin is closed so a prompt
+// fails instead of waiting for input.
+pub fn run(command: &mut Command, limits: &Limits) -> Result<Output, RunError> {
+    if limits.cancel.is_cancelled() {
+        return Err(RunError::Interrupted(Interruption::Cancelled));
+    }
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(RunError::Io)?;
+
+    // Read both pipes while waiting, a child writing more than the pipe
+    // buffer holds would otherwise never exit
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let deadline = Instant::now() + limits.timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(e) => {
+                stop(&mut child);
+                return Err(RunError::Io(e));
+            }
+        }
+
+        let interruption = if limits.cancel.is_cancelled() {
+            Some(Interruption::Cancelled)
+        } else if Instant::now() >= deadline {
+            Some(Interruption::TimedOut(limits.timeout))
+        } else {
+            None
+        };
+        if let Some(interruption) = interruption {
+            stop(&mut child);
+            return Err(RunError::Interrupted(interruption));
+        }
+
+        thread::sleep(POLL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: collect(stdout),
+        stderr: collect(stderr),
+    })
+}
+
+// Async version of `run` on tokio's process support, so waiting doesn't tie
+// up a thread. The child is also killed when the returned future is dropped.
+pub async fn run_async(mut command: tokio::process::Command, limits: &Limits) -> Result<Output, RunError> {
+    if limits.cancel.is_cancelled() {
+        return Err(RunError::Interrupted(Interruption::Cancelled));
+    }
+
+    let child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(RunError::Io)?;
+
+    // Losing the race drops the child, which kills it
+    tokio::select! {
+        output = child.wait_with_output() => output.map_err(RunError::Io),
+        _ = tokio::time::sleep(limits.timeout) => Err(RunError::Interrupted(Interruption::TimedOut(limits.timeout))),
+        _ = limits.cancel.cancelled() => Err(RunError::Interrupted(Interruption::Cancelled)),
+    }
+}
+
+fn stop(child: &mut Child) {
+    if let Err(e) = child.kill() {
+        eprintln!("Warning: Failed to stop process {}: {}", child.id(), e);
+    }
+    // Reap it so no zombie is left behind
+    let _ = child.wait();
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> Option<JoinHandle<Vec<u8>>> {
+    let mut pipe = pipe?;
+    Some(thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    }))
+}
+
+fn collect(reader: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    reader.and_then(|handle| handle.join().ok()).unwrap_or_default()
+}
+
+// Long running operations the frontend may cancel, keyed by an id the
+// frontend picks when starting them
+#[derive(Default)]
+pub struct Operations {
+    running: Mutex<HashMap<String, CancelToken>>,
+}
+
+impl Operations {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, CancelToken>> {
+        match self.running.lock() {
+            Ok(guard) => guard,
+            // The map only holds tokens, keep using it
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    // Register an operation. It stays cancellable until the returned guard
+    // is dropped.
+    pub fn begin<'a>(&'a self, id: &str) -> Operation<'a> {
+        let token = CancelToken::default();
+        self.lock().insert(id.to_string(), token.clone());
+        Operation {
+            operations: self,
+            id: id.to_string(),
+            token,
+        }
+    }
+
+    // Cancel a running operation, false when no operation has that id
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.lock().get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub struct Operation<'a> {
+    operations: &'a Operations,
+    id: String,
+    token: CancelToken,
+}
+
+impl Operation<'_> {
+    pub fn token(&self) -> CancelToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for Operation<'_> {
+    fn drop(&mut self) {
+        let mut running = self.operations.lock();
+        // A newer operation may have reused the id
+        if running.get(&self.id).is_some_and(|token| Arc::ptr_eq(&token.0, &self.token.0)) {
+            running.remove(&self.id);
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    // Well below the child's sleep, so a test only passes if it was stopped
+    const SHORT: Duration = Duration::from_millis(200);
+    const BOUND: Duration = Duration::from_secs(3);
+
+    fn sleep_command() -> Command {
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        command
+    }
+
+    fn interruption(result: Result<Output, RunError>) -> Option<Interruption> {
+        match result {
+            Err(RunError::Interrupted(interruption)) => Some(interruption),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn collects_output() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo out; echo err >&2; exit 3"]);
+        let output = match run(&mut command, &Limits::timeout(BOUND)) {
+            Ok(output) => output,
+            Err(_) => panic!("sh did not run"),
+        };
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[test]
+    fn kills_hung_child_at_timeout() {
+        let started = Instant::now();
+        let result = run(&mut sleep_command(), &Limits::timeout(SHORT));
+        assert_eq!(interruption(result), Some(Interruption::TimedOut(SHORT)));
+        assert!(started.elapsed() < BOUND);
+    }
+
+    #[test]
+    fn cancel_token_stops_child() {
+        let limits = Limits::timeout(Duration::from_secs(60));
+        let cancel = limits.cancel.clone();
+        let canceller = thread::spawn(move || {
+            thread::sleep(SHORT);
+            cancel.cancel();
+        });
+
+        let started = Instant::now();
+        let result = run(&mut sleep_command(), &limits);
+        assert_eq!(interruption(result), Some(Interruption::Cancelled));
+        assert!(started.elapsed() < BOUND);
+        canceller.join().unwrap();
+
+        // Nothing is started once cancelled
+        let mut missing = Command::new("/nonexistent/tock");
+        assert_eq!(interruption(run(&mut missing, &limits)), Some(Interruption::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn async_run_is_stopped_too() {
+        let started = Instant::now();
+        let result = run_async(tokio::process::Command::from(sleep_command()), &Limits::timeout(SHORT)).await;
+        assert_eq!(interruption(result), Some(Interruption::TimedOut(SHORT)));
+
+        let limits = Limits::timeout(Duration::from_secs(60));
+        let cancel = limits.cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(SHORT).await;
+            cancel.cancel();
+        });
+        let result = run_async(tokio::process::Command::from(sleep_command()), &limits).await;
+        assert_eq!(interruption(result), Some(Interruption::Cancelled));
+        assert!(started.elapsed() < BOUND * 2);
+    }
+
+    #[test]
+    fn releases_operation_ids() {
+        let operations = Operations::default();
+        let first = operations.begin("month");
+        assert!(operations.cancel("month"));
+        assert!(first.token().is_cancelled());
+        drop(first);
+        assert!(!operations.cancel("month"));
+
+        // A finished operation doesn't release an id reused by a newer one
+        let older = operations.begin("month");
+        let newer = operations.begin("month");
+        drop(older);
+        assert!(operations.cancel("month"));
+        assert!(newer.token().is_cancelled());
+        drop(newer);
+        assert!(!operations.cancel("month"));
+        assert!(operations.lock().is_empty());
+    }
+}
@@ -0,0 +1,517 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use specta::Type;
+
+use crate::error::{AppError, AppResult};
+use crate::settings::Rounding;
+use crate::{parser, Activity, CommandResult};
+
+// File formats supported by `save_report_to_file`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Text,
+    Csv,
+    Json,
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "txt" | "text" => Ok(ExportFormat::Text),
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "html" => Ok(ExportFormat::Html),
+            other => Err(format!("Unsupported report format: {}. Use txt, csv, json, markdown or html.", other)),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Text => "txt",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+// Longest range accepted for a single report, to keep CLI usage bounded
+pub const MAX_RANGE_DAYS: i64 = 366;
+
+#[derive(Debug, Serialize, Clone, Type)]
+pub struct DayReport {
+    pub date: NaiveDate,
+    pub activities: Vec<Activity>,
+    pub total_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Clone, Type)]
+pub struct ProjectTotal {
+    pub project: String,
+    pub total_seconds: i64,
+}
+
+// Activities between two dates aggregated per day and per project
+#[derive(Debug, Serialize, Clone, Type)]
+pub struct RangeReport {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub days: Vec<DayReport>,
+    pub project_totals: Vec<ProjectTotal>,
+    pub total_seconds: i64,
+}
+
+// Validate a "YYYY-MM-DD" start/end pair
+pub fn parse_range(start_date: &str, end_date: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let start = NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid start date: {}. Expected YYYY-MM-DD.", start_date))?;
+    let end = NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid end date: {}. Expected YYYY-MM-DD.", end_date))?;
+
+    if end < start {
+        return Err("End date must not be before start date".to_string());
+    }
+
+    if (end - start).num_days() >= MAX_RANGE_DAYS {
+        return Err(format!("Date range is too long (max {} days)", MAX_RANGE_DAYS));
+    }
+
+    Ok((start, end))
+}
+
+impl RangeReport {
+    // Build from the per-day report output of a backend. Fails on the first
+    // day whose report could not be generated. Each activity's duration is
+    // rounded, and totals add up the rounded durations.
+    pub fn from_daily_reports(
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        daily: Vec<(NaiveDate, CommandResult)>,
+        rounding: &Rounding,
+    ) -> AppResult<Self> {
+        let mut days = Vec::new();
+
+        for (date, result) in daily {
+            let output = result.into_result().map_err(|e| match e {
+                AppError::TockCommand(message) => {
+                    AppError::TockCommand(format!("Failed to generate report for {}: {}", date, message))
+                }
+                other => other,
+            })?;
+
+            let mut activities = parser::parse_report(&output, date);
+            for activity in &mut activities {
+                activity.duration_seconds = activity.duration_seconds.map(|seconds| rounding.apply(seconds));
+            }
+            if activities.is_empty() {
+                continue;
+            }
+
+            let total_seconds = activities.iter().filter_map(|a| a.duration_seconds).sum();
+            days.push(DayReport {
+                date,
+                activities,
+                total_seconds,
+            });
+        }
+
+        let mut project_totals: Vec<ProjectTotal> = Vec::new();
+        for activity in days.iter().flat_map(|d| d.activities.iter()) {
+            let seconds = activity.duration_seconds.unwrap_or(0);
+            match project_totals.iter_mut().find(|p| p.project == activity.project) {
+                Some(total) => total.total_seconds += seconds,
+                None => project_totals.push(ProjectTotal {
+                    project: activity.project.clone(),
+                    total_seconds: seconds,
+                }),
+            }
+        }
+        project_totals.sort_by(|a, b| a.project.cmp(&b.project));
+
+        let total_seconds = days.iter().map(|d| d.total_seconds).sum();
+
+        Ok(RangeReport {
+            start_date,
+            end_date,
+            days,
+            project_totals,
+            total_seconds,
+        })
+    }
+
+    // Plain text rendering used for the .txt export
+    pub fn to_text(&self) -> String {
+        let title = self.title();
+        let mut output = format!("{}\n{}\n", title, "=".repeat(title.chars().count()));
+
+        if self.days.is_empty() {
+            output.push_str("\nNo activities recorded in this period.\n");
+            return output;
+        }
+
+        for day in &self.days {
+            output.push_str(&format!("\n=== {} ===\n", day.date.format("%Y-%m-%d (%A)")));
+
+            for activity in &day.activities {
+                output.push_str(&format!(
+                    "  {} - {} ({}) | {} | {}\n",
+                    format_time(activity.start_time),
+                    format_time(activity.end_time),
+                    format_hours_minutes(activity.duration_seconds.unwrap_or(0)),
+                    activity.project,
+                    activity.description
+                ));
+            }
+
+            output.push_str(&format!("  Day total: {}\n", format_hours_minutes(day.total_seconds)));
+        }
+
+        output.push_str("\nTotals by project\n-----------------\n");
+        for project in &self.project_totals {
+            output.push_str(&format!("{}: {}\n", project.project, format_hours_minutes(project.total_seconds)));
+        }
+
+        output.push_str(&format!("\nTotal: {}\n", format_hours_minutes(self.total_seconds)));
+        output
+    }
+
+    pub fn render(&self, format: ExportFormat) -> AppResult<String> {
+        match format {
+            ExportFormat::Text => Ok(self.to_text()),
+            ExportFormat::Csv => Ok(self.to_csv()),
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            ExportFormat::Markdown => Ok(self.to_markdown()),
+            ExportFormat::Html => Ok(self.to_html()),
+        }
+    }
+
+    fn title(&self) -> String {
+        if self.start_date == self.end_date {
+            format!("Time Tracking Report: {}", self.start_date.format("%Y-%m-%d"))
+        } else {
+            format!(
+                "Time Tracking Report: {} to {}",
+                self.start_date.format("%Y-%m-%d"),
+                self.end_date.format("%Y-%m-%d")
+            )
+        }
+    }
+
+    // One row per activity
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from("project,description,start,end,minutes\n");
+
+        for activity in self.days.iter().flat_map(|d| d.activities.iter()) {
+            output.push_str(&format!(
+                "{},{},{},{},{}\n",
+                csv_field(&activity.project),
+                csv_field(&activity.description),
+                format_datetime(activity.start_time),
+                format_datetime(activity.end_time),
+                activity.duration_seconds.unwrap_or(0) / 60
+            ));
+        }
+
+        output
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut output = format!("# {}\n", self.title());
+
+        if self.days.is_empty() {
+            output.push_str("\nNo activities recorded in this period.\n");
+            return output;
+        }
+
+        for day in &self.days {
+            output.push_str(&format!("\n## {}\n\n", day.date.format("%Y-%m-%d (%A)")));
+            output.push_str("| Start | End | Duration | Project | Description |\n");
+            output.push_str("|-------|-----|----------|---------|-------------|\n");
+
+            for activity in &day.activities {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    format_time(activity.start_time),
+                    format_time(activity.end_time),
+                    format_hours_minutes(activity.duration_seconds.unwrap_or(0)),
+                    markdown_cell(&activity.project),
+                    markdown_cell(&activity.description)
+                ));
+            }
+
+            output.push_str(&format!("\n**Day total:** {}\n", format_hours_minutes(day.total_seconds)));
+        }
+
+        output.push_str("\n## Totals by project\n\n| Project | Total |\n|---------|-------|\n");
+        for project in &self.project_totals {
+            output.push_str(&format!(
+                "| {} | {} |\n",
+                markdown_cell(&project.project),
+                format_hours_minutes(project.total_seconds)
+            ));
+        }
+
+        output.push_str(&format!("\n**Total:** {}\n", format_hours_minutes(self.total_seconds)));
+        output
+    }
+
+    // Self-contained page with inline styles so it can be opened from email
+    pub fn to_html(&self) -> String {
+        let title = html_escape(&self.title());
+        let mut body = String::new();
+
+        if self.days.is_empty() {
+            body.push_str("<p>No activities recorded in this period.</p>\n");
+        }
+
+        for day in &self.days {
+            body.push_str(&format!("<h2>{}</h2>\n<table>\n", day.date.format("%Y-%m-%d (%A)")));
+            body.push_str("<tr><th>Start</th><th>End</th><th>Duration</th><th>Project</th><th>Description</th></tr>\n");
+
+            for activity in &day.activities {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                    format_time(activity.start_time),
+                    format_time(activity.end_time),
+                    format_hours_minutes(activity.duration_seconds.unwrap_or(0)),
+                    html_escape(&activity.project),
+                    html_escape(&activity.description)
+                ));
+            }
+
+            body.push_str(&format!(
+                "<tr class=\"total\"><td colspan=\"2\">Day total</td><td colspan=\"3\">{}</td></tr>\n</table>\n",
+                format_hours_minutes(day.total_seconds)
+            ));
+        }
+
+        if !self.project_totals.is_empty() {
+            body.push_str("<h2>Totals by project</h2>\n<table>\n<tr><th>Project</th><th>Total</th></tr>\n");
+            for project in &self.project_totals {
+                body.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td></tr>\n",
+                    html_escape(&project.project),
+                    format_hours_minutes(project.total_seconds)
+                ));
+            }
+            body.push_str(&format!(
+                "<tr class=\"total\"><td>Total</td><td>{}</td></tr>\n</table>\n",
+                format_hours_minutes(self.total_seconds)
+            ));
+        }
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", Roboto, sans-serif; color: #1e293b; margin: 2rem; }}
+h1 {{ font-size: 1.5rem; }}
+h2 {{ font-size: 1.1rem; margin-top: 1.5rem; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border: 1px solid #e2e8f0; padding: 0.4rem 0.6rem; text-align: left; }}
+th {{ background: #f1f5f9; }}
+tr.total td {{ font-weight: bold; background: #f8fafc; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{body}</body>
+</html>
+"#
+        )
+    }
+}
+
+fn format_datetime(value: Option<chrono::NaiveDateTime>) -> String {
+    value.map(|v| v.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default()
+}
+
+fn format_time(value: Option<chrono::NaiveDateTime>) -> String {
+    value.map(|v| v.format("%H:%M").to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+fn html_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+pub fn format_hours_minutes(total_seconds: i64) -> String {
+    format!("{}h {}m", total_seconds / 3600, (total_seconds % 3600) / 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::RoundingMode;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn ok(output: &str) -> CommandResult {
+        CommandResult {
+            success: true,
+            output: output.to_string(),
+            error: None,
+            interrupted: None,
+        }
+    }
+
+    #[test]
+    fn aggregates_rounded_durations() {
+        let daily = vec![
+            (
+                date("2026-01-01"),
+                ok("📁 zeta: 0h 22m\n   09:00 - 09:22 (0h 22m) | a\n📁 alpha: 0h 7m\n   10:00 - 10:07 (0h 7m) | b\n"),
+            ),
+            (date("2026-01-02"), ok("📊 Time Tracking Report\nNo activities\n")),
+            (date("2026-01-03"), ok("📁 zeta: 0h 50m\n   13:00 - 13:50 (0h 50m) | c\n")),
+        ];
+        let rounding = Rounding { minutes: 15, mode: RoundingMode::Nearest };
+        let report = RangeReport::from_daily_reports(date("2026-01-01"), date("2026-01-03"), daily, &rounding).unwrap();
+
+        // Days without activities are left out
+        let days: Vec<_> = report.days.iter().map(|d| (d.date.to_string(), d.total_seconds / 60)).collect();
+        assert_eq!(days, [("2026-01-01".to_string(), 15), ("2026-01-03".to_string(), 45)]);
+        // Rounded before adding up: 22m + 7m is 15m + 0m, not 30m
+        let minutes: Vec<_> = report.days[0].activities.iter().map(|a| a.duration_seconds.unwrap() / 60).collect();
+        assert_eq!(minutes, [15, 0]);
+
+        let projects: Vec<_> = report.project_totals.iter().map(|p| (p.project.as_str(), p.total_seconds / 60)).collect();
+        assert_eq!(projects, [("alpha", 0), ("zeta", 60)]);
+        assert_eq!(report.total_seconds, 60 * 60);
+    }
+
+    #[test]
+    fn names_the_day_that_failed() {
+        let failed = CommandResult {
+            success: false,
+            output: String::new(),
+            error: Some("invalid date\n".to_string()),
+            interrupted: None,
+        };
+        let daily = vec![(date("2026-01-01"), ok("")), (date("2026-01-02"), failed)];
+        match RangeReport::from_daily_reports(date("2026-01-01"), date("2026-01-02"), daily, &Rounding::default()) {
+            Err(AppError::TockCommand(message)) => {
+                assert_eq!(message, "Failed to generate report for 2026-01-02: invalid date")
+            }
+            other => panic!("expected a tock error, got {:?}", other.map(|r| r.total_seconds)),
+        }
+    }
+
+    // Single-day report with one activity
+    fn report_of(project: &str, description: &str) -> RangeReport {
+        let start = chrono::NaiveDateTime::parse_from_str("2026-01-01 09:00", "%Y-%m-%d %H:%M").unwrap();
+        let activity = Activity {
+            project: project.to_string(),
+            description: description.to_string(),
+            start_time: Some(start),
+            end_time: Some(start + chrono::Duration::minutes(90)),
+            duration_seconds: Some(90 * 60),
+        };
+        RangeReport {
+            start_date: date("2026-01-01"),
+            end_date: date("2026-01-01"),
+            days: vec![DayReport {
+                date: date("2026-01-01"),
+                activities: vec![activity],
+                total_seconds: 90 * 60,
+            }],
+            project_totals: vec![ProjectTotal {
+                project: project.to_string(),
+                total_seconds: 90 * 60,
+            }],
+            total_seconds: 90 * 60,
+        }
+    }
+
+    fn empty_report() -> RangeReport {
+        RangeReport {
+            start_date: date("2026-01-01"),
+            end_date: date("2026-01-07"),
+            days: Vec::new(),
+            project_totals: Vec::new(),
+            total_seconds: 0,
+        }
+    }
+
+    #[test]
+    fn escapes_csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+
+        let csv = report_of("acme, inc", "review \"v2\"\nand fix").to_csv();
+        assert_eq!(
+            csv,
+            "project,description,start,end,minutes\n\
+             \"acme, inc\",\"review \"\"v2\"\"\nand fix\",2026-01-01 09:00,2026-01-01 10:30,90\n"
+        );
+        assert_eq!(empty_report().to_csv(), "project,description,start,end,minutes\n");
+    }
+
+    #[test]
+    fn escapes_markdown_cells() {
+        assert_eq!(markdown_cell("a|b"), "a\\|b");
+        assert_eq!(markdown_cell("two\nlines"), "two lines");
+
+        let markdown = report_of("a|b", "first\nsecond").to_markdown();
+        assert!(markdown.contains("| 09:00 | 10:30 | 1h 30m | a\\|b | first second |\n"));
+        assert!(markdown.contains("| a\\|b | 1h 30m |\n"));
+        assert_eq!(
+            empty_report().to_markdown(),
+            "# Time Tracking Report: 2026-01-01 to 2026-01-07\n\nNo activities recorded in this period.\n"
+        );
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(html_escape(r#"<b>&"'"#), "&lt;b&gt;&amp;&quot;&#39;");
+
+        let html = report_of("<script>", "Tom & Jerry's \"show\"").to_html();
+        assert!(html.contains("<td>&lt;script&gt;</td><td>Tom &amp; Jerry&#39;s &quot;show&quot;</td>"));
+        assert!(!html.contains("<script>"));
+
+        let empty = empty_report().to_html();
+        assert!(empty.contains("<h1>Time Tracking Report: 2026-01-01 to 2026-01-07</h1>"));
+        assert!(empty.contains("<p>No activities recorded in this period.</p>"));
+        assert!(!empty.contains("<table>"));
+    }
+
+    #[test]
+    fn renders_empty_text_and_json() {
+        let title = "Time Tracking Report: 2026-01-01 to 2026-01-07";
+        assert_eq!(
+            empty_report().to_text(),
+            format!("{}\n{}\n\nNo activities recorded in this period.\n", title, "=".repeat(title.len()))
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&empty_report().render(ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["days"], serde_json::json!([]));
+        assert_eq!(json["total_seconds"], 0);
+    }
+}
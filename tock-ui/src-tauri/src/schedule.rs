@@ -0,0 +1,226 @@
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::str::FromStr;
+
+use crate::period::PeriodKind;
+
+// When automatic report sending runs. Stored as JSON in
+// report_settings.schedule_config, e.g.
+//   {"type": "weekly", "weekday": "friday", "time": "17:00"}
+//   {"type": "cron", "expression": "0 17 * * FRI"}
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReportSchedule {
+    // As soon as the current reporting period has closed
+    #[default]
+    PeriodEnd,
+    // A fixed number of days after the previous run
+    Interval { days: u32 },
+    // Every week on a weekday at a local time ("HH:MM")
+    Weekly { weekday: String, time: String },
+    // Every month on a day (clamped to the month's length) at a local time
+    MonthlyDay { day: u32, time: String },
+    // The last Monday-Friday of every month at a local time
+    LastWorkingDay { time: String },
+    // Standard 5-field cron expression in local time
+    Cron { expression: String },
+}
+
+impl ReportSchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ReportSchedule::PeriodEnd => Ok(()),
+            ReportSchedule::Interval { days } => {
+                if *days == 0 {
+                    Err("Interval must be at least 1 day".to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            ReportSchedule::Weekly { weekday, time } => {
+                parse_weekday(weekday)?;
+                parse_time(time).map(|_| ())
+            }
+            ReportSchedule::MonthlyDay { day, time } => {
+                if !(1..=31).contains(day) {
+                    return Err(format!("Invalid day of month: {}. Use a day between 1 and 31.", day));
+                }
+                parse_time(time).map(|_| ())
+            }
+            ReportSchedule::LastWorkingDay { time } => parse_time(time).map(|_| ()),
+            ReportSchedule::Cron { expression } => parse_cron(expression).map(|_| ()),
+        }
+    }
+
+    // First time strictly after `after` at which the schedule fires.
+    // `period` is the configured reporting period, used by PeriodEnd.
+    pub fn next_after(&self, after: DateTime<Local>, period: PeriodKind) -> Result<DateTime<Local>, String> {
+        let date = after.date_naive();
+
+        let next = match self {
+            ReportSchedule::PeriodEnd => {
+                let next_start = period.containing(date).end + Duration::days(1);
+                Some(local(next_start.and_time(NaiveTime::MIN)))
+            }
+            ReportSchedule::Interval { days } => Some(after + Duration::days(*days as i64)),
+            ReportSchedule::Weekly { weekday, time } => {
+                let weekday = parse_weekday(weekday)?;
+                let time = parse_time(time)?;
+                (0..=7)
+                    .map(|offset| date + Duration::days(offset))
+                    .filter(|d| d.weekday() == weekday)
+                    .map(|d| local(d.and_time(time)))
+                    .find(|dt| *dt > after)
+            }
+            ReportSchedule::MonthlyDay { day, time } => {
+                let time = parse_time(time)?;
+                months_from(date)
+                    .map(|(year, month)| {
+                        let day = (*day).min(last_day_of_month(year, month).day());
+                        local(NaiveDate::from_ymd_opt(year, month, day).unwrap_or(date).and_time(time))
+                    })
+                    .find(|dt| *dt > after)
+            }
+            ReportSchedule::LastWorkingDay { time } => {
+                let time = parse_time(time)?;
+                months_from(date)
+                    .map(|(year, month)| {
+                        let mut day = last_day_of_month(year, month);
+                        while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                            day -= Duration::days(1);
+                        }
+                        local(day.and_time(time))
+                    })
+                    .find(|dt| *dt > after)
+            }
+            ReportSchedule::Cron { expression } => parse_cron(expression)?.after(&after).next(),
+        };
+
+        next.ok_or_else(|| "Schedule has no upcoming run".to_string())
+    }
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    Weekday::from_str(value.trim()).map_err(|_| format!("Invalid weekday: {}", value))
+}
+
+fn parse_time(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid time: {}. Expected HH:MM.", value))
+}
+
+// The cron crate expects a leading seconds field; accept the common
+// 5-field form as well
+fn parse_cron(expression: &str) -> Result<cron::Schedule, String> {
+    let expression = expression.trim();
+    let full = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    cron::Schedule::from_str(&full).map_err(|e| format!("Invalid cron expression '{}': {}", expression, e))
+}
+
+// Resolve a local wall-clock time. Times skipped by a DST change move
+// forward by an hour.
+fn local(naive: NaiveDateTime) -> DateTime<Local> {
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .or_else(|| Local.from_local_datetime(&(naive + Duration::hours(1))).earliest())
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+// The month of `date` and the two following months as (year, month)
+fn months_from(date: NaiveDate) -> impl Iterator<Item = (i32, u32)> {
+    (0..3).map(move |offset| {
+        let index = date.year() * 12 + date.month0() as i32 + offset;
+        (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+    })
+}
+
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .expect("last day of month is always valid")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Local> {
+        local(NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap())
+    }
+
+    // Next run after `after` as local "YYYY-MM-DD HH:MM"
+    fn next(schedule: &ReportSchedule, after: &str) -> String {
+        schedule
+            .next_after(at(after), PeriodKind::IsoWeek)
+            .unwrap()
+            .naive_local()
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    #[test]
+    fn monthly_day_is_clamped_to_month_length() {
+        let schedule = ReportSchedule::MonthlyDay { day: 31, time: "09:00".to_string() };
+        assert_eq!(next(&schedule, "2027-01-31 10:00"), "2027-02-28 09:00");
+        assert_eq!(next(&schedule, "2028-02-15 10:00"), "2028-02-29 09:00");
+        assert_eq!(next(&schedule, "2026-04-01 10:00"), "2026-04-30 09:00");
+        // Strictly after
+        assert_eq!(next(&schedule, "2026-04-30 09:00"), "2026-05-31 09:00");
+    }
+
+    #[test]
+    fn last_working_day_skips_weekends() {
+        let schedule = ReportSchedule::LastWorkingDay { time: "17:00".to_string() };
+        // October 2026 ends on a Saturday, January 2027 on a Sunday
+        assert_eq!(next(&schedule, "2026-10-17 12:00"), "2026-10-30 17:00");
+        assert_eq!(next(&schedule, "2026-10-30 18:00"), "2026-11-30 17:00");
+        assert_eq!(next(&schedule, "2027-01-02 12:00"), "2027-01-29 17:00");
+    }
+
+    #[test]
+    fn weekly_on_the_same_weekday() {
+        let schedule = ReportSchedule::Weekly { weekday: "friday".to_string(), time: "17:00".to_string() };
+        // 2026-10-16 is a Friday
+        assert_eq!(next(&schedule, "2026-10-16 09:00"), "2026-10-16 17:00");
+        assert_eq!(next(&schedule, "2026-10-16 17:00"), "2026-10-23 17:00");
+        assert_eq!(next(&schedule, "2026-10-16 18:00"), "2026-10-23 17:00");
+        assert_eq!(next(&schedule, "2026-10-17 09:00"), "2026-10-23 17:00");
+
+        let invalid = ReportSchedule::Weekly { weekday: "someday".to_string(), time: "17:00".to_string() };
+        assert!(invalid.validate().is_err());
+        assert!(invalid.next_after(at("2026-10-16 09:00"), PeriodKind::Month).is_err());
+        let invalid = ReportSchedule::Weekly { weekday: "friday".to_string(), time: "5pm".to_string() };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn interval_and_period_end() {
+        let schedule = ReportSchedule::Interval { days: 3 };
+        assert_eq!(next(&schedule, "2026-10-16 12:34"), "2026-10-19 12:34");
+        assert!(ReportSchedule::Interval { days: 0 }.validate().is_err());
+
+        // The Monday after the ISO week
+        assert_eq!(next(&ReportSchedule::PeriodEnd, "2026-10-16 12:00"), "2026-10-19 00:00");
+    }
+
+    #[test]
+    fn cron_expressions() {
+        let cron = |expression: &str| ReportSchedule::Cron { expression: expression.to_string() };
+        // 5-field expressions get a leading seconds field
+        assert_eq!(next(&cron("0 17 * * FRI"), "2026-10-16 09:00"), "2026-10-16 17:00");
+        assert_eq!(next(&cron(" 30 8 1 * * "), "2026-10-16 09:00"), "2026-11-01 08:30");
+        assert_eq!(next(&cron("0 0 17 * * FRI"), "2026-10-16 17:00"), "2026-10-23 17:00");
+
+        for invalid in ["61 17 * * *", "not a cron", "0 17 * *"] {
+            assert!(cron(invalid).validate().is_err(), "{} accepted", invalid);
+            assert!(cron(invalid).next_after(at("2026-10-16 09:00"), PeriodKind::Month).is_err());
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use chrono::Datelike;
+use tauri::{AppHandle, Emitter};
+
+use crate::db::JobState;
+use crate::error::AppResult;
+use crate::get_db;
+
+// Background jobs run from a loop started in `run()`, independent of the
+// window being visible. Job state is persisted in the scheduler_jobs table so
+// intervals survive restarts, and every finished run is emitted to the
+// frontend as a `scheduler-job-finished` event carrying the JobState.
+
+pub const JOB_FINISHED_EVENT: &str = "scheduler-job-finished";
+
+// How often the loop checks for due jobs
+const TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+enum Job {
+    // Deliver queued reports and send scheduled ones
+    AutoSend,
+    // Refresh cached projects from all enabled API routes
+    ProjectSync,
+    // Keep the current month's calendar data cached
+    CacheWarm,
+    // Snapshot tock_ui.db into the backups folder
+    Backup,
+}
+
+impl Job {
+    const ALL: [Job; 4] = [Job::AutoSend, Job::ProjectSync, Job::CacheWarm, Job::Backup];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Job::AutoSend => "auto_send",
+            Job::ProjectSync => "project_sync",
+            Job::CacheWarm => "cache_warm",
+            Job::Backup => "backup",
+        }
+    }
+
+    fn interval(&self) -> chrono::Duration {
+        match self {
+            // The report schedule itself decides whether anything is sent
+            Job::AutoSend => chrono::Duration::minutes(5),
+            Job::ProjectSync => chrono::Duration::hours(6),
+            Job::CacheWarm => chrono::Duration::minutes(15),
+            Job::Backup => chrono::Duration::hours(24),
+        }
+    }
+
+    async fn run(&self) -> AppResult<String> {
+        match self {
+            Job::AutoSend => crate::check_and_send_auto_report().await,
+            Job::ProjectSync => crate::sync_all_api_projects().await,
+            Job::CacheWarm => {
+                let now = chrono::Local::now();
+                crate::month_activities(now.year() as u32, now.month(), None, None)
+                    .await
+                    .map(|_| format!("Cached {:04}-{:02}", now.year(), now.month()))
+            }
+            Job::Backup => {
+                let path = get_db().backup("scheduled")?;
+                Ok(format!("Backup saved to: {}", path.display()))
+            }
+        }
+    }
+
+    fn is_due(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let next_run_at = match get_db().get_job_state(self.name()) {
+            Ok(Some(state)) => state.next_run_at,
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("Warning: Failed to read state of job {}: {}", self.name(), e);
+                None
+            }
+        };
+
+        match next_run_at.and_then(|value| chrono::DateTime::parse_from_rfc3339(&value).ok()) {
+            Some(next) => next <= now,
+            None => true, // Never ran
+        }
+    }
+}
+
+// Start the scheduler loop. Every job runs once shortly after startup unless
+// its persisted next run is still in the future.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            for job in Job::ALL {
+                if job.is_due(chrono::Utc::now()) {
+                    run_job(&app, job).await;
+                }
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+async fn run_job(app: &AppHandle, job: Job) {
+    let result = job.run().await;
+    let finished_at = chrono::Utc::now();
+
+    let state = JobState {
+        name: job.name().to_string(),
+        last_run_at: Some(finished_at.to_rfc3339()),
+        next_run_at: Some((finished_at + job.interval()).to_rfc3339()),
+        last_success: Some(result.is_ok()),
+        last_message: Some(match result {
+            Ok(message) => message,
+            Err(e) => e.to_string(),
+        }),
+    };
+
+    if let Err(e) = get_db().save_job_state(&state) {
+        eprintln!("Warning: Failed to save state of job {}: {}", job.name(), e);
+    }
+
+    if let Err(e) = app.emit(JOB_FINISHED_EVENT, &state) {
+        eprintln!("Warning: Failed to emit {} event: {}", JOB_FINISHED_EVENT, e);
+    }
+}
@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::backend;
+use crate::db::Database;
+use crate::error::{AppError, AppResult};
+use crate::process;
+use crate::report::ExportFormat;
+
+// Upper bound for tock_timeout_seconds
+const MAX_TOCK_TIMEOUT_SECONDS: u32 = 600;
+
+// Values of week_start, as the calendar in HistoryTab.tsx expects them
+const WEEKDAYS: [&str; 7] = ["sunday", "monday", "tuesday", "wednesday", "thursday", "friday", "saturday"];
+
+// Typed user preferences. Every field is stored as its own row in
+// user_preferences under the field's name: strings as-is, so existing keys
+// such as `tracker_backend` keep working, anything else as JSON. Missing or
+// unreadable rows fall back to the default of that field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Settings {
+    // Format preselected when saving a report: txt, csv, json, markdown or html
+    pub default_report_format: String,
+    // Folder reports are saved to, the home directory when unset
+    pub report_folder_path: Option<String>,
+    // First day of the week in calendars
+    pub week_start: String,
+    pub theme: Theme,
+    // Time tracking backend: cli or native
+    pub tracker_backend: String,
+    // Activity file used by the native backend, TOCK_FILE or ~/.tock.txt when unset
+    pub tock_file_path: Option<String>,
+    pub rounding: Rounding,
+    // Seconds a tock command may run before it is stopped
+    pub tock_timeout_seconds: u32,
+    // tock executable, searched for on PATH and in the usual go and Homebrew
+    // folders when unset
+    pub tock_binary_path: Option<String>,
+    // Extra environment variables for every tock command, e.g. TOCK_FILE
+    pub tock_env: BTreeMap<String, String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            default_report_format: "txt".to_string(),
+            report_folder_path: None,
+            week_start: "sunday".to_string(),
+            theme: Theme::default(),
+            tracker_backend: "cli".to_string(),
+            tock_file_path: None,
+            rounding: Rounding::default(),
+            tock_timeout_seconds: process::DEFAULT_TIMEOUT.as_secs() as u32,
+            tock_binary_path: None,
+            tock_env: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+// How activity durations are rounded in reports
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct Rounding {
+    // Round to this many minutes, 0 disables rounding
+    pub minutes: u32,
+    pub mode: RoundingMode,
+}
+
+impl Rounding {
+    // `seconds` rounded to a multiple of `minutes`
+    pub fn apply(&self, seconds: i64) -> i64 {
+        if self.minutes == 0 {
+            return seconds;
+        }
+        let step = i64::from(self.minutes) * 60;
+        let seconds = seconds.max(0);
+        let steps = match self.mode {
+            RoundingMode::Nearest => (seconds + step / 2) / step,
+            RoundingMode::Up => (seconds + step - 1) / step,
+            RoundingMode::Down => seconds / step,
+        };
+        steps * step
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    #[default]
+    Nearest,
+    Up,
+    Down,
+}
+
+impl Settings {
+    pub fn load(db: &Database) -> Settings {
+        let defaults = match serde_json::to_value(Settings::default()) {
+            Ok(Value::Object(map)) => map,
+            _ => return Settings::default(),
+        };
+
+        let mut merged = defaults.clone();
+        for key in defaults.keys() {
+            let stored = match db.get_preference(key) {
+                Ok(Some(value)) => value,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Warning: Failed to read setting {}: {}", key, e);
+                    continue;
+                }
+            };
+
+            // Plain strings are stored without JSON quoting, try them first
+            let mut candidates = vec![Value::String(stored.clone())];
+            candidates.extend(serde_json::from_str(&stored).ok());
+
+            let accepted = candidates.into_iter().find(|value| {
+                let mut attempt = merged.clone();
+                attempt.insert(key.clone(), value.clone());
+                match serde_json::from_value::<Settings>(Value::Object(attempt)) {
+                    Ok(settings) => settings.check_values().is_ok(),
+                    Err(_) => false,
+                }
+            });
+            match accepted {
+                Some(value) => {
+                    merged.insert(key.clone(), value);
+                }
+                None => eprintln!("Warning: Ignoring invalid value for setting {}", key),
+            }
+        }
+
+        serde_json::from_value(Value::Object(merged)).unwrap_or_default()
+    }
+
+    // Validate and store every field
+    pub fn save(&self, db: &Database) -> AppResult<()> {
+        self.validate().map_err(AppError::Validation)?;
+
+        let fields: Map<String, Value> = match serde_json::to_value(self)? {
+            Value::Object(map) => map,
+            _ => return Err(AppError::Internal("Settings must serialize to an object".to_string())),
+        };
+
+        db.transaction(|tx| {
+            let now = chrono::Local::now().to_rfc3339();
+            for (key, value) in &fields {
+                if value.is_null() {
+                    tx.execute("DELETE FROM user_preferences WHERE key = ?1", [key])?;
+                    continue;
+                }
+                let stored = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                tx.execute(
+                    "INSERT OR REPLACE INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, ?3)",
+                    [key, &stored, &now],
+                )?;
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    // Activity file preference for ActivityFile::locate. A TOCK_FILE set for
+    // tock commands applies to the native backend as well.
+    pub fn activity_file_preference(&self) -> Option<&str> {
+        self.tock_file_path
+            .as_deref()
+            .filter(|p| !p.trim().is_empty())
+            .or_else(|| self.tock_env.get("TOCK_FILE").map(String::as_str))
+    }
+
+    pub fn tock_timeout(&self) -> Duration {
+        Duration::from_secs(u64::from(self.tock_timeout_seconds))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.check_values()?;
+
+        if let Some(path) = self.tock_binary_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            if !Path::new(path).is_file() {
+                return Err(format!("No tock executable at {}", path));
+            }
+        }
+
+        Ok(())
+    }
+
+    // The checks of validate that don't depend on the system, also applied
+    // to stored values when loading
+    fn check_values(&self) -> Result<(), String> {
+        ExportFormat::parse(&self.default_report_format)?;
+
+        if !WEEKDAYS.contains(&self.week_start.as_str()) {
+            return Err(format!(
+                "Invalid week start day: {}. Use one of: {}",
+                self.week_start,
+                WEEKDAYS.join(", ")
+            ));
+        }
+
+        if !backend::available_backends().contains(&self.tracker_backend.as_str()) {
+            return Err(format!(
+                "Unknown tracker backend: {}. Use one of: {}",
+                self.tracker_backend,
+                backend::available_backends().join(", ")
+            ));
+        }
+
+        if self.rounding.minutes > 60 {
+            return Err("Rounding must be at most 60 minutes".to_string());
+        }
+
+        if !(1..=MAX_TOCK_TIMEOUT_SECONDS).contains(&self.tock_timeout_seconds) {
+            return Err(format!(
+                "The tock timeout must be between 1 and {} seconds",
+                MAX_TOCK_TIMEOUT_SECONDS
+            ));
+        }
+
+        for name in self.tock_env.keys() {
+            if name.is_empty() || name.contains('=') || name.contains('\0') {
+                return Err(format!("Invalid environment variable name: {:?}", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rounding(minutes: u32, mode: RoundingMode) -> Rounding {
+        Rounding { minutes, mode }
+    }
+
+    #[test]
+    fn rounds_durations() {
+        // 22 minutes 30 seconds
+        let seconds = 22 * 60 + 30;
+        assert_eq!(rounding(0, RoundingMode::Up).apply(seconds), seconds);
+        assert_eq!(rounding(15, RoundingMode::Nearest).apply(seconds), 30 * 60);
+        assert_eq!(rounding(15, RoundingMode::Nearest).apply(22 * 60), 15 * 60);
+        assert_eq!(rounding(15, RoundingMode::Up).apply(seconds), 30 * 60);
+        assert_eq!(rounding(15, RoundingMode::Up).apply(15 * 60), 15 * 60);
+        assert_eq!(rounding(15, RoundingMode::Down).apply(seconds), 15 * 60);
+    }
+
+    #[test]
+    fn validates_settings() {
+        assert_eq!(Settings::default().validate(), Ok(()));
+        let with = |change: fn(&mut Settings)| {
+            let mut settings = Settings::default();
+            change(&mut settings);
+            settings.validate()
+        };
+
+        assert_eq!(with(|s| s.week_start = "monday".to_string()), Ok(()));
+        // Only the names the calendar recognizes
+        assert!(with(|s| s.week_start = "Mon".to_string()).is_err());
+        assert!(with(|s| s.week_start = "MONDAY".to_string()).is_err());
+        assert!(with(|s| s.week_start = "Monday".to_string()).is_err());
+        assert!(with(|s| s.default_report_format = "pdf".to_string()).is_err());
+        assert!(with(|s| s.tracker_backend = "sqlite".to_string()).is_err());
+        assert!(with(|s| s.rounding.minutes = 61).is_err());
+        assert!(with(|s| s.tock_timeout_seconds = 0).is_err());
+        assert!(with(|s| s.tock_timeout_seconds = MAX_TOCK_TIMEOUT_SECONDS + 1).is_err());
+        assert!(with(|s| s.tock_binary_path = Some("/nonexistent/tock".to_string())).is_err());
+        assert!(with(|s| {
+            s.tock_env.insert("A=B".to_string(), "c".to_string());
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn load_falls_back_on_invalid_stored_values() {
+        let db = Database::in_memory().unwrap();
+        let stored = [
+            ("week_start", "Mon"),
+            ("tock_timeout_seconds", "soon"),
+            ("rounding", r#"{"minutes":90,"mode":"up"}"#),
+            ("tracker_backend", "native"),
+            ("theme", "dark"),
+            ("default_report_format", "csv"),
+        ];
+        db.transaction(|tx| {
+            for (key, value) in stored {
+                tx.execute(
+                    "INSERT INTO user_preferences (key, value, updated_at) VALUES (?1, ?2, '')",
+                    [key, value],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let settings = Settings::load(&db);
+        let defaults = Settings::default();
+        assert_eq!(settings.week_start, defaults.week_start);
+        assert_eq!(settings.tock_timeout_seconds, defaults.tock_timeout_seconds);
+        assert_eq!(settings.rounding, defaults.rounding);
+        // Valid values are kept, strings stored without JSON quoting included
+        assert_eq!(settings.tracker_backend, "native");
+        assert_eq!(settings.theme, Theme::Dark);
+        assert_eq!(settings.default_report_format, "csv");
+
+        // Saved values load back unchanged
+        let mut changed = settings.clone();
+        changed.week_start = "monday".to_string();
+        changed.rounding = rounding(15, RoundingMode::Up);
+        changed.save(&db).unwrap();
+        assert_eq!(Settings::load(&db), changed);
+    }
+}
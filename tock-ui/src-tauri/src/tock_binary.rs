@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::settings::Settings;
+
+// Windows-specific constant for process creation optimization
+// CREATE_NO_WINDOW (0x08000000) - Prevents creating a new console window
+// This significantly reduces overhead on Windows
+#[cfg(target_os = "windows")]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+// Finding the tock executable. Apps started from the macOS Finder or a
+// desktop launcher don't get the PATH of a login shell, so besides PATH the
+// places `go install` and Homebrew put binaries are searched as well.
+
+// How tock is started: which executable and with what extra environment
+#[derive(Debug, Clone)]
+pub struct TockBinary {
+    program: PathBuf,
+    env: BTreeMap<String, String>,
+}
+
+impl TockBinary {
+    // The executable from settings, otherwise the first one found
+    pub fn from_settings(settings: &Settings) -> Self {
+        let program = match settings.tock_binary_path.as_deref().filter(|p| !p.trim().is_empty()) {
+            Some(path) => PathBuf::from(path),
+            // Let the OS report it missing when it is nowhere to be found
+            None => discover("tock").unwrap_or_else(|| PathBuf::from("tock")),
+        };
+        TockBinary {
+            program,
+            env: settings.tock_env.clone(),
+        }
+    }
+
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    pub fn command<S: AsRef<OsStr>>(&self, args: &[S]) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(args).envs(&self.env);
+
+        // On Windows, prevent a console window from flashing for every call
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(CREATE_NO_WINDOW);
+
+        cmd
+    }
+}
+
+// Executable `name` on PATH or in a common install location
+pub fn discover(name: &str) -> Option<PathBuf> {
+    find(name, &search_dirs(|key| env::var_os(key), dirs::home_dir()))
+}
+
+// First of `dirs` that holds the executable `name`
+fn find(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    let file_name = executable_name(name);
+    dirs.iter().map(|dir| dir.join(&file_name)).find(|path| path.is_file())
+}
+
+fn executable_name(name: &str) -> String {
+    if cfg!(target_os = "windows") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// Folders searched in order. `var` reads an environment variable.
+fn search_dirs(var: impl Fn(&str) -> Option<OsString>, home: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = match var("PATH") {
+        Some(path) => env::split_paths(&path).collect(),
+        None => Vec::new(),
+    };
+
+    // `go install` uses GOBIN, then the bin folder of each GOPATH entry,
+    // then ~/go/bin
+    if let Some(gobin) = var("GOBIN") {
+        candidates.push(PathBuf::from(gobin));
+    }
+    if let Some(gopath) = var("GOPATH") {
+        candidates.extend(env::split_paths(&gopath).map(|path| path.join("bin")));
+    }
+    if let Some(home) = home {
+        candidates.push(home.join("go").join("bin"));
+    }
+
+    // Homebrew on Apple silicon, on Intel Macs and on Linux, and Go itself
+    // when installed from go.dev
+    #[cfg(not(target_os = "windows"))]
+    candidates.extend(
+        [
+            "/opt/homebrew/bin",
+            "/usr/local/bin",
+            "/home/linuxbrew/.linuxbrew/bin",
+            "/usr/local/go/bin",
+        ]
+        .map(PathBuf::from),
+    );
+    #[cfg(target_os = "windows")]
+    candidates.push(PathBuf::from(r"C:\Program Files\Go\bin"));
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // Environment with only the given variables set
+    fn vars(vars: &[(&str, OsString)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: BTreeMap<String, OsString> = vars.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+        move |key| vars.get(key).cloned()
+    }
+
+    fn paths(dirs: &[&Path]) -> OsString {
+        env::join_paths(dirs).unwrap()
+    }
+
+    #[test]
+    fn searches_path_then_go_folders() {
+        let root = Path::new("/test");
+        let env = vars(&[
+            ("PATH", paths(&[&root.join("a"), &root.join("b")])),
+            ("GOBIN", root.join("gobin").into_os_string()),
+            ("GOPATH", paths(&[&root.join("go1"), &root.join("go2")])),
+        ]);
+        let dirs = search_dirs(env, Some(root.join("home")));
+        assert_eq!(
+            dirs[..6],
+            [
+                root.join("a"),
+                root.join("b"),
+                root.join("gobin"),
+                root.join("go1").join("bin"),
+                root.join("go2").join("bin"),
+                root.join("home").join("go").join("bin"),
+            ]
+        );
+
+        // Without any of them only the fixed install locations are left
+        let fixed = search_dirs(vars(&[]), None);
+        assert_eq!(dirs[6..], fixed[..]);
+    }
+
+    #[test]
+    fn finds_first_executable_in_search_order() {
+        let root = std::env::temp_dir().join(format!("tock-ui-discover-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let folders = ["path", "gobin", "gopath", "home"].map(|name| root.join(name));
+        for folder in &folders {
+            fs::create_dir_all(folder).unwrap();
+        }
+        fs::create_dir_all(root.join("gopath").join("bin")).unwrap();
+        fs::create_dir_all(root.join("home").join("go").join("bin")).unwrap();
+
+        let env = vars(&[
+            ("PATH", root.join("path").into_os_string()),
+            ("GOBIN", root.join("gobin").into_os_string()),
+            ("GOPATH", root.join("gopath").into_os_string()),
+        ]);
+        let dirs = search_dirs(env, Some(root.join("home")));
+        let tock = executable_name("tock-ui-test-tock");
+        let install = |dir: PathBuf| {
+            let path = dir.join(&tock);
+            fs::write(&path, "").unwrap();
+            path
+        };
+
+        assert_eq!(find("tock-ui-test-tock", &dirs), None);
+        let home = install(root.join("home").join("go").join("bin"));
+        assert_eq!(find("tock-ui-test-tock", &dirs), Some(home));
+        let gopath = install(root.join("gopath").join("bin"));
+        assert_eq!(find("tock-ui-test-tock", &dirs), Some(gopath));
+        let gobin = install(root.join("gobin"));
+        assert_eq!(find("tock-ui-test-tock", &dirs), Some(gobin));
+        let on_path = install(root.join("path"));
+        assert_eq!(find("tock-ui-test-tock", &dirs), Some(on_path));
+
+        // A folder with that name is not an executable
+        fs::remove_file(root.join("path").join(&tock)).unwrap();
+        fs::create_dir(root.join("path").join(&tock)).unwrap();
+        assert_eq!(find("tock-ui-test-tock", &dirs), Some(root.join("gobin").join(&tock)));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
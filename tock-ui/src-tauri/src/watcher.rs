@@ -0,0 +1,133 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+use specta::Type;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::{AppHandle, Emitter};
+
+use crate::activity_file::{self, ActivityFile};
+use crate::invalidation::DateRange;
+use crate::settings::Settings;
+use crate::{get_db, invalidate_caches_for};
+
+// Watches the tock activity file for changes made outside tock-ui, e.g. by
+// running `tock` in a terminal. The file's modification time is polled; when
+// it moves, the lines are compared with the previous read, the caches for the
+// days those lines cover are invalidated and a `tock-data-changed` event is
+// emitted so open views can refresh.
+
+pub const DATA_CHANGED_EVENT: &str = "tock-data-changed";
+
+// How often the modification time is checked
+const POLL: std::time::Duration = std::time::Duration::from_secs(2);
+
+// Payload of the `tock-data-changed` event. Both dates are null when the
+// changed days could not be worked out and every cache was invalidated.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct TockDataChanged {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+}
+
+struct Snapshot {
+    path: PathBuf,
+    modified: Option<(SystemTime, u64)>, // None while the file does not exist
+    lines: HashSet<String>,
+}
+
+enum Change {
+    Days(DateRange),
+    Unknown,
+}
+
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut previous: Option<Snapshot> = None;
+        loop {
+            if let Some(change) = check(&mut previous) {
+                notify(&app, change);
+            }
+            tokio::time::sleep(POLL).await;
+        }
+    });
+}
+
+// Compare the activity file with the previous snapshot. The first snapshot,
+// and the first one after the file was moved in settings, only record state.
+fn check(previous: &mut Option<Snapshot>) -> Option<Change> {
+    let settings = Settings::load(get_db());
+    let file = ActivityFile::locate(settings.activity_file_preference());
+    let path = file.path().to_path_buf();
+    let modified = modified(&path);
+
+    if let Some(snapshot) = previous {
+        if snapshot.path == path && snapshot.modified == modified {
+            return None;
+        }
+    }
+
+    let lines = read_lines(&path);
+    let change = match previous.take() {
+        Some(snapshot) if snapshot.path == path => changed_days(&snapshot.lines, &lines),
+        _ => None,
+    };
+
+    *previous = Some(Snapshot { path, modified, lines });
+    change
+}
+
+fn notify(app: &AppHandle, change: Change) {
+    let payload = match change {
+        Change::Days(range) => {
+            invalidate_caches_for(Some(range));
+            TockDataChanged {
+                start_date: Some(range.start),
+                end_date: Some(range.end),
+            }
+        }
+        Change::Unknown => {
+            invalidate_caches_for(None);
+            TockDataChanged {
+                start_date: None,
+                end_date: None,
+            }
+        }
+    };
+
+    if let Err(e) = app.emit(DATA_CHANGED_EVENT, &payload) {
+        eprintln!("Warning: Failed to emit {} event: {}", DATA_CHANGED_EVENT, e);
+    }
+}
+
+fn modified(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+fn read_lines(path: &Path) -> HashSet<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+// Days covered by lines that were added or removed. An edited line shows up
+// as both, so the old and the new times count.
+fn changed_days(before: &HashSet<String>, after: &HashSet<String>) -> Option<Change> {
+    let mut dates = Vec::new();
+    for line in before.symmetric_difference(after) {
+        let activity = match activity_file::parse_line(line) {
+            Some(activity) => activity,
+            None => return Some(Change::Unknown),
+        };
+        dates.extend(activity.start_time.map(|t| t.date()));
+        dates.extend(activity.end_time.map(|t| t.date()));
+    }
+    DateRange::covering(dates).map(Change::Days)
+}
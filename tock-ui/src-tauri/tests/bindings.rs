@@ -0,0 +1,24 @@
+// src/bindings.ts is generated from the command signatures and checked in so
+// the frontend type-checks without running the app. Fails when it is stale.
+// Regenerate with:
+//   UPDATE_BINDINGS=1 cargo test --test bindings
+
+use std::path::Path;
+
+#[test]
+fn bindings_are_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(tock_ui_lib::BINDINGS_PATH);
+    let generated = tock_ui_lib::typescript_bindings().expect("Failed to generate TypeScript bindings");
+
+    if std::env::var_os("UPDATE_BINDINGS").is_some() {
+        std::fs::write(&path, &generated).expect("Failed to write TypeScript bindings");
+        return;
+    }
+
+    let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        checked_in == generated,
+        "{} is out of date with the Rust commands. Run `UPDATE_BINDINGS=1 cargo test --test bindings` in src-tauri to regenerate it.",
+        path.display()
+    );
+}
@@ -1,10 +1,21 @@
 import { useState, useEffect } from "react";
-import { tockCommands } from "./api";
+import { errorMessage, isAppError, tockCommands } from "./api";
 import "./App.css";
 import { TabSelector } from "./components/TabSelector";
 import { ActivityTab } from "./components/ActivityTab";
 import { HistoryTab } from "./components/HistoryTab";
 import { SettingsTab } from "./components/SettingsTab";
+import { Settings, Theme, TockCapabilities } from "./types";
+
+// Why the installed tock may not work as expected, if it might not
+const compatibilityWarning = (capabilities: TockCapabilities) => {
+  switch (capabilities.compatibility) {
+    case "too_new":
+      return `tock ${capabilities.version} was found, which is newer than supported. Tock UI supports versions before ${capabilities.max_version}; some features may not work.`;
+    default:
+      return null;
+  }
+};
 
 function App() {
   const INSTALLATION_CHECK_DELAY = 2000; // ms - delay before rechecking installation after auto-install
@@ -13,36 +24,68 @@ function App() {
   const [message, setMessage] = useState<{ type: "success" | "error"; text: string } | null>(null);
   const [tockInstalled, setTockInstalled] = useState<boolean | null>(null);
   const [isInstalling, setIsInstalling] = useState<boolean>(false);
+  const [tockWarning, setTockWarning] = useState<string | null>(null);
+  const [theme, setTheme] = useState<Theme>("system");
 
   useEffect(() => {
+    // Project sync and auto-send run in the backend scheduler
     checkInstallation();
-    syncApiProjectsOnStartup();
-    checkAndSendAutoReport();
+    checkDatabase();
+    loadTheme();
   }, []);
 
+  // Dark mode follows the theme setting, or the system while it is "system"
+  useEffect(() => {
+    const systemDark = window.matchMedia("(prefers-color-scheme: dark)");
+    const apply = () => {
+      const dark = theme === "dark" || (theme === "system" && systemDark.matches);
+      document.documentElement.classList.toggle("dark", dark);
+    };
+    apply();
+    systemDark.addEventListener("change", apply);
+    return () => systemDark.removeEventListener("change", apply);
+  }, [theme]);
+
+  const loadTheme = async () => {
+    try {
+      setTheme((await tockCommands.getSettings()).theme);
+    } catch (error) {
+      console.error("Failed to load settings:", error);
+    }
+  };
+
   const checkInstallation = async () => {
-    const result = await tockCommands.checkTockInstalled();
-    setTockInstalled(result.success);
-    if (!result.success) {
-      setMessage({ type: "error", text: result.error || "Tock CLI not found" });
+    try {
+      await tockCommands.checkTockInstalled();
+      setTockInstalled(true);
+      checkCompatibility();
+    } catch (error) {
+      // Only a missing binary needs the installation screen
+      setTockInstalled(!(isAppError(error) && error.code === "tock_not_installed"));
+      setMessage({ type: "error", text: errorMessage(error) });
     }
   };
   
-  const syncApiProjectsOnStartup = async () => {
-    // Silently sync all API projects in the background
+  const checkCompatibility = async () => {
     try {
-      await tockCommands.syncAllApiProjects();
+      // Detect again, tock may have been updated since the last check
+      setTockWarning(compatibilityWarning(await tockCommands.getTockCapabilities(true)));
     } catch (error) {
-      console.error('Failed to sync API projects on startup:', error);
+      console.error("Failed to detect tock capabilities:", error);
     }
   };
 
-  const checkAndSendAutoReport = async () => {
-    // Silently check and send auto report in the background
+  const checkDatabase = async () => {
     try {
-      await tockCommands.checkAndSendAutoReport();
+      const info = await tockCommands.getDatabaseInfo();
+      if (info.error) {
+        setMessage({
+          type: "error",
+          text: `${info.error}. Changes to settings will not be saved.`,
+        });
+      }
     } catch (error) {
-      console.error('Failed to check and send auto report on startup:', error);
+      setMessage({ type: "error", text: `Failed to open database: ${errorMessage(error)}` });
     }
   };
 
@@ -51,18 +94,14 @@ function App() {
     setMessage({ type: "success", text: "Installing Tock CLI... This may take a few moments." });
     
     try {
-      const result = await tockCommands.autoInstallTock();
-      if (result.success) {
-        setMessage({ type: "success", text: result.output || "Tock installed successfully!" });
-        // Wait a moment then check installation again
-        setTimeout(async () => {
-          await checkInstallation();
-        }, INSTALLATION_CHECK_DELAY);
-      } else {
-        setMessage({ type: "error", text: result.error || "Installation failed" });
-      }
+      const output = await tockCommands.autoInstallTock();
+      setMessage({ type: "success", text: output || "Tock installed successfully!" });
+      // Wait a moment then check installation again
+      setTimeout(async () => {
+        await checkInstallation();
+      }, INSTALLATION_CHECK_DELAY);
     } catch (error) {
-      setMessage({ type: "error", text: `Installation error: ${error}` });
+      setMessage({ type: "error", text: `Installation failed: ${errorMessage(error)}` });
     } finally {
       setIsInstalling(false);
     }
@@ -165,6 +204,12 @@ function App() {
           </div>
         )}
 
+        {tockWarning && (
+          <div className="mb-6 px-6 py-4 rounded-lg font-medium bg-amber-100 text-amber-800 border border-amber-300">
+            {tockWarning}
+          </div>
+        )}
+
         {/* Tab Selector */}
         <TabSelector activeTab={activeTab} onTabChange={setActiveTab} />
 
@@ -172,7 +217,9 @@ function App() {
         <div className="bg-white rounded-lg shadow-lg p-6 min-h-125">
           {activeTab === "activity" && <ActivityTab showMessage={showMessage} />}
           {activeTab === "history" && <HistoryTab />}
-          {activeTab === "settings" && <SettingsTab showMessage={showMessage} />}
+          {activeTab === "settings" && (
+            <SettingsTab showMessage={showMessage} onSettingsSaved={(settings: Settings) => setTheme(settings.theme)} />
+          )}
         </div>
 
         {/* Footer */}
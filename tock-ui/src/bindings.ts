@@ -0,0 +1,296 @@
+// @ts-nocheck
+// Regenerate with `UPDATE_BINDINGS=1 cargo test --test bindings` in src-tauri.
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async startActivity(project: string, description: string, time: string | null) : Promise<string> {
+    return await TAURI_INVOKE("start_activity", { project, description, time });
+},
+async stopActivity(time: string | null) : Promise<string> {
+    return await TAURI_INVOKE("stop_activity", { time });
+},
+async addActivity(project: string, description: string, start: string, end: string | null, duration: string | null) : Promise<string> {
+    return await TAURI_INVOKE("add_activity", { project, description, start, end, duration });
+},
+async continueActivity(index: number | null, description: string | null, project: string | null, time: string | null) : Promise<string> {
+    return await TAURI_INVOKE("continue_activity", { index, description, project, time });
+},
+async getCurrentActivity() : Promise<string> {
+    return await TAURI_INVOKE("get_current_activity");
+},
+async getRecentActivities(number: number | null) : Promise<string> {
+    return await TAURI_INVOKE("get_recent_activities", { number });
+},
+async getReport(dateType: string, date: string | null) : Promise<string> {
+    return await TAURI_INVOKE("get_report", { dateType, date });
+},
+async getAvailableBackends() : Promise<BackendChoice> {
+    return await TAURI_INVOKE("get_available_backends");
+},
+async getCurrentActivityStructured() : Promise<Activity[]> {
+    return await TAURI_INVOKE("get_current_activity_structured");
+},
+async getRecentActivitiesStructured(number: number | null) : Promise<Activity[]> {
+    return await TAURI_INVOKE("get_recent_activities_structured", { number });
+},
+async getReportStructured(dateType: string, date: string | null) : Promise<Activity[]> {
+    return await TAURI_INVOKE("get_report_structured", { dateType, date });
+},
+async checkTockInstalled() : Promise<string> {
+    return await TAURI_INVOKE("check_tock_installed");
+},
+async detectTockPath() : Promise<string | null> {
+    return await TAURI_INVOKE("detect_tock_path");
+},
+async getTockCapabilities(refresh: boolean | null) : Promise<TockCapabilities> {
+    return await TAURI_INVOKE("get_tock_capabilities", { refresh });
+},
+async autoInstallTock() : Promise<string> {
+    return await TAURI_INVOKE("auto_install_tock");
+},
+async getActivitiesForDate(date: string) : Promise<string> {
+    return await TAURI_INVOKE("get_activities_for_date", { date });
+},
+async getActivitiesForMonth(year: number, month: number, operationId: string | null) : Promise<string> {
+    return await TAURI_INVOKE("get_activities_for_month", { year, month, operationId });
+},
+async getActivitiesForMonthStructured(year: number, month: number, operationId: string | null) : Promise<Activity[]> {
+    return await TAURI_INVOKE("get_activities_for_month_structured", { year, month, operationId });
+},
+async cancelOperation(operationId: string) : Promise<boolean> {
+    return await TAURI_INVOKE("cancel_operation", { operationId });
+},
+async getRangeReport(startDate: string, endDate: string) : Promise<RangeReport> {
+    return await TAURI_INVOKE("get_range_report", { startDate, endDate });
+},
+async saveReportToFile(dateType: string, date: string | null, startDate: string | null, endDate: string | null, customPath: string | null, format: string | null) : Promise<string> {
+    return await TAURI_INVOKE("save_report_to_file", { dateType, date, startDate, endDate, customPath, format });
+},
+async addFavorite(name: string, description: string) : Promise<null> {
+    return await TAURI_INVOKE("add_favorite", { name, description });
+},
+async removeFavorite(name: string, description: string) : Promise<null> {
+    return await TAURI_INVOKE("remove_favorite", { name, description });
+},
+async isFavorite(name: string, description: string) : Promise<boolean> {
+    return await TAURI_INVOKE("is_favorite", { name, description });
+},
+async getAllFavorites() : Promise<FavoriteProject[]> {
+    return await TAURI_INVOKE("get_all_favorites");
+},
+async addApiRoute(name: string, url: string, auth: ApiAuth | null) : Promise<null> {
+    return await TAURI_INVOKE("add_api_route", { name, url, auth });
+},
+async updateApiRoute(id: number, name: string, url: string, enabled: boolean, auth: ApiAuth | null) : Promise<null> {
+    return await TAURI_INVOKE("update_api_route", { id, name, url, enabled, auth });
+},
+async deleteApiRoute(id: number) : Promise<null> {
+    return await TAURI_INVOKE("delete_api_route", { id });
+},
+async getAllApiRoutes() : Promise<ApiRoute[]> {
+    return await TAURI_INVOKE("get_all_api_routes");
+},
+async fetchProjectsFromApi(url: string, apiRouteId: number | null) : Promise<string> {
+    return await TAURI_INVOKE("fetch_projects_from_api", { url, apiRouteId });
+},
+async addReportApiRoute(name: string, url: string, auth: ApiAuth | null) : Promise<null> {
+    return await TAURI_INVOKE("add_report_api_route", { name, url, auth });
+},
+async updateReportApiRoute(id: number, name: string, url: string, enabled: boolean, auth: ApiAuth | null) : Promise<null> {
+    return await TAURI_INVOKE("update_report_api_route", { id, name, url, enabled, auth });
+},
+async deleteReportApiRoute(id: number) : Promise<null> {
+    return await TAURI_INVOKE("delete_report_api_route", { id });
+},
+async getAllReportApiRoutes() : Promise<ApiRoute[]> {
+    return await TAURI_INVOKE("get_all_report_api_routes");
+},
+async getReportSettings() : Promise<ReportSettings> {
+    return await TAURI_INVOKE("get_report_settings");
+},
+async updateReportSettings(autoSendEnabled: boolean, selectedApiRouteId: number | null) : Promise<null> {
+    return await TAURI_INVOKE("update_report_settings", { autoSendEnabled, selectedApiRouteId });
+},
+async sendMonthlyReportToApi(apiRouteId: number, closed: boolean | null) : Promise<string> {
+    return await TAURI_INVOKE("send_monthly_report_to_api", { apiRouteId, closed });
+},
+async updateReportPeriodSettings(periodType: string, cutoffDay: number | null, sendPartial: boolean) : Promise<null> {
+    return await TAURI_INVOKE("update_report_period_settings", { periodType, cutoffDay, sendPartial });
+},
+async updateReportSchedule(schedule: ReportSchedule) : Promise<null> {
+    return await TAURI_INVOKE("update_report_schedule", { schedule });
+},
+async getNextReportDue() : Promise<NextReportDue> {
+    return await TAURI_INVOKE("get_next_report_due");
+},
+async checkAndSendAutoReport() : Promise<string> {
+    return await TAURI_INVOKE("check_and_send_auto_report");
+},
+async getOutbox() : Promise<OutboxEntry[]> {
+    return await TAURI_INVOKE("get_outbox");
+},
+async retryOutboxEntry(id: number) : Promise<string> {
+    return await TAURI_INVOKE("retry_outbox_entry", { id });
+},
+async discardOutboxEntry(id: number) : Promise<null> {
+    return await TAURI_INVOKE("discard_outbox_entry", { id });
+},
+async getReportDeliveryHistory(limit: number | null) : Promise<ReportDelivery[]> {
+    return await TAURI_INVOKE("get_report_delivery_history", { limit });
+},
+async getSchedulerJobs() : Promise<JobState[]> {
+    return await TAURI_INVOKE("get_scheduler_jobs");
+},
+async getDatabaseInfo() : Promise<DatabaseInfo> {
+    return await TAURI_INVOKE("get_database_info");
+},
+async createBackup() : Promise<string> {
+    return await TAURI_INVOKE("create_backup");
+},
+async listBackups() : Promise<BackupInfo[]> {
+    return await TAURI_INVOKE("list_backups");
+},
+async restoreBackup(fileName: string) : Promise<string> {
+    return await TAURI_INVOKE("restore_backup", { fileName });
+},
+async exportSettingsBundle(customPath: string | null, includeSecrets: boolean | null) : Promise<string> {
+    return await TAURI_INVOKE("export_settings_bundle", { customPath, includeSecrets });
+},
+async importSettingsBundle(path: string, mode: string | null) : Promise<ImportReport> {
+    return await TAURI_INVOKE("import_settings_bundle", { path, mode });
+},
+async getCacheStats() : Promise<CacheStats> {
+    return await TAURI_INVOKE("get_cache_stats");
+},
+async getCalendarCache(yearMonth: string) : Promise<string | null> {
+    return await TAURI_INVOKE("get_calendar_cache", { yearMonth });
+},
+async saveCalendarCache(yearMonth: string, data: string) : Promise<null> {
+    return await TAURI_INVOKE("save_calendar_cache", { yearMonth, data });
+},
+async clearCalendarCache(yearMonth: string) : Promise<null> {
+    return await TAURI_INVOKE("clear_calendar_cache", { yearMonth });
+},
+async getCachedProjects(apiRouteId: number | null) : Promise<CachedProject[]> {
+    return await TAURI_INVOKE("get_cached_projects", { apiRouteId });
+},
+async syncApiProjects(apiRouteId: number) : Promise<string> {
+    return await TAURI_INVOKE("sync_api_projects", { apiRouteId });
+},
+async syncAllApiProjects() : Promise<string> {
+    return await TAURI_INVOKE("sync_all_api_projects");
+},
+async deleteCachedProjectsByApi(apiRouteId: number) : Promise<null> {
+    return await TAURI_INVOKE("delete_cached_projects_by_api", { apiRouteId });
+},
+async getSettings() : Promise<Settings> {
+    return await TAURI_INVOKE("get_settings");
+},
+async updateSettings(settings: Settings) : Promise<Settings> {
+    return await TAURI_INVOKE("update_settings", { settings });
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type Activity = { project: string; description: string; start_time: string | null; end_time: string | null; duration_seconds: number | null }
+export type ApiAuth = { type: "none" } | { type: "bearer"; token: string } | { type: "basic"; username: string; password: string } | { type: "header"; name: string; value: string } | { type: "query_key"; name: string; value: string }
+export type ApiRoute = { id: number | null; name: string; url: string; enabled: boolean; created_at: string; auth?: ApiAuth }
+export type AppError = { code: AppErrorCode; message: string }
+export type AppErrorCode = "tock_not_installed" | "tock_command_failed" | "install_failed" | "timeout" | "cancelled" | "validation" | "not_found" | "network" | "database_locked" | "database" | "io" | "serialization" | "internal"
+export type BackendChoice = { selected: string; available: string[] }
+export type BackupInfo = { file_name: string; path: string; created_at: string; size_bytes: number }
+export type CacheStats = { entries: number; capacity: number; hits: number; misses: number; evictions: number; expirations: number; invalidations: number; memory_bytes: number }
+export type CachedProject = { id: number | null; name: string; description: string; source_api_route_id: number | null; last_synced: string }
+export type Compatibility = "supported" | "too_new" | "unknown"
+export type DatabaseInfo = { path: string | null; in_memory: boolean; error: string | null }
+export type DayReport = { date: string; activities: Activity[]; total_seconds: number }
+export type FavoriteProject = { id: number | null; name: string; description: string; created_at: string }
+export type ImportConflict = { table: string; key: string; reason: string }
+export type ImportReport = { favorites: number; api_routes: number; report_api_routes: number; cached_projects: number; preferences: number; report_settings: boolean; conflicts: ImportConflict[] }
+export type JobState = { name: string; last_run_at: string | null; next_run_at: string | null; last_success: boolean | null; last_message: string | null }
+export type MonthLoadProgress = { operation_id: string | null; date: string; completed: number; total: number; activities: Activity[] }
+export type NextReportDue = { auto_send_enabled: boolean; schedule: ReportSchedule; last_run_at: string | null; next_due_at: string }
+export type OutboxEntry = { id: number | null; api_route_id: number; period: string; partial: boolean; payload: string; attempts: number; next_attempt_at: string | null; last_error: string | null; created_at: string }
+export type ProjectTotal = { project: string; total_seconds: number }
+export type RangeReport = { start_date: string; end_date: string; days: DayReport[]; project_totals: ProjectTotal[]; total_seconds: number }
+export type ReportDelivery = { id: number | null; attempted_at: string; api_route_id: number; route_name: string; route_url: string; period: string; partial: boolean; payload_hash: string; success: boolean; status_code: number | null; response_body: string | null; error: string | null }
+export type ReportSchedule = { type: "period_end" } | { type: "interval"; days: number } | { type: "weekly"; weekday: string; time: string } | { type: "monthly_day"; day: number; time: string } | { type: "last_working_day"; time: string } | { type: "cron"; expression: string }
+export type ReportSettings = { id: number | null; auto_send_enabled: boolean; selected_api_route_id: number | null; last_sent_at: string | null; period_type: string; cutoff_day: number | null; send_partial: boolean; last_closed_period: string | null; schedule?: ReportSchedule; last_auto_run_at: string | null; updated_at: string }
+export type Rounding = { minutes: number; mode: RoundingMode }
+export type RoundingMode = "nearest" | "up" | "down"
+export type Settings = { default_report_format: string; report_folder_path: string | null; week_start: string; theme: Theme; tracker_backend: string; tock_file_path: string | null; rounding: Rounding; tock_timeout_seconds: number; tock_binary_path: string | null; tock_env: Partial<{ [key in string]: string }> }
+export type Theme = "system" | "light" | "dark"
+export type TockCapabilities = { path: string; version_output: string; version: string | null; compatibility: Compatibility; max_version: string; commands: string[]; continue_activity: boolean; add_duration: boolean; report_date: boolean; json_output: boolean }
+export type TockDataChanged = { start_date: string | null; end_date: string | null }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
@@ -1,8 +1,9 @@
 import React, { useState, useEffect } from 'react';
-import { tockCommands } from '../api';
+import { listen } from '@tauri-apps/api/event';
+import { errorMessage, tockCommands } from '../api';
 import { Play, Square, Plus, Clock, FolderOpen, Star } from 'lucide-react';
 import { ProjectSelectionModal } from './ProjectSelectionModal';
-import { ApiRoute, FavoriteProject } from '../types';
+import { Activity, ApiRoute, FavoriteProject, TockDataChanged } from '../types';
 
 interface ActivityTabProps {
   showMessage: (type: 'success' | 'error', text: string) => void;
@@ -43,6 +44,16 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
     loadRecentProjects();
     loadFavorites();
     loadApiRoutes();
+
+    // Refresh when tock is used outside the app
+    const unlisten = listen<TockDataChanged>('tock-data-changed', () => {
+      refreshCurrentActivities();
+      loadRecentProjects();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
 
   // Check if current project/description is a favorite when they change
@@ -50,96 +61,51 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
     checkIfFavorite();
   }, [project, description]);
 
-  const parseActivityOutput = (output: string): ParsedActivity[] => {
-    const lines = output.trim().split('\n');
-    const activities: ParsedActivity[] = [];
-    
-    // Skip empty output
-    if (!output.trim() || output.includes('No current activity')) {
-      return activities;
+  // Format a duration in seconds as e.g. "1h3m", "12m5s" or "45s"
+  const formatDuration = (totalSeconds: number | null): string => {
+    if (totalSeconds === null) {
+      return '';
     }
-    
-    for (let i = 0; i < lines.length; i++) {
-      const line = lines[i].trim();
-      
-      // Skip header line
-      if (i === 0 && (line.includes('Start') && line.includes('Description') && line.includes('Project'))) {
-        continue;
-      }
-      
-      // Skip empty lines
-      if (!line) {
-        continue;
-      }
-      
-      // Use regex to parse the line format
-      // Expected format: "YYYY-MM-DD HH:MM  description  project  duration"
-      const regex = /^(\d{4}-\d{2}-\d{2} \d{2}:\d{2})\s+(\S+)\s+(\S+)\s+(\S+)$/;
-      const match = line.match(regex);
-      
-      if (match) {
-        const [, start, description, project, duration] = match;
-        
-        // Format duration nicely
-        let displayDuration = duration;
-        if (duration.endsWith('s') && !duration.includes('m') && !duration.includes('h')) {
-          const seconds = parseInt(duration);
-          if (!isNaN(seconds)) {
-            if (seconds >= 3600) {
-              const hours = Math.floor(seconds / 3600);
-              const minutes = Math.floor((seconds % 3600) / 60);
-              const remainingSeconds = seconds % 60;
-              displayDuration = `${hours}h${minutes > 0 ? `${minutes}m` : ''}${remainingSeconds > 0 ? `${remainingSeconds}s` : ''}`;
-            } else if (seconds >= 60) {
-              const minutes = Math.floor(seconds / 60);
-              const remainingSeconds = seconds % 60;
-              displayDuration = `${minutes}m${remainingSeconds > 0 ? `${remainingSeconds}s` : ''}`;
-            }
-          }
-        }
-        
-        activities.push({
-          start,
-          description,
-          project,
-          duration: displayDuration,
-        });
-      } else {
-        // Try a more flexible regex if the first one fails
-        const flexibleRegex = /^(\d{4}-\d{2}-\d{2} \d{2}:\d{2})\s+([^\s]+(?:\s+[^\s]+)*?)\s+([^\s]+)\s+([^\s]+)$/;
-        const flexibleMatch = line.match(flexibleRegex);
-        
-        if (flexibleMatch) {
-          const [, start, description, project, duration] = flexibleMatch;
-          activities.push({
-            start,
-            description: description.trim(),
-            project,
-            duration,
-          });
-        } else {
-          console.log('Could not parse line:', line);
-        }
-      }
+    const hours = Math.floor(totalSeconds / 3600);
+    const minutes = Math.floor((totalSeconds % 3600) / 60);
+    const seconds = totalSeconds % 60;
+    if (hours > 0) {
+      return `${hours}h${minutes > 0 ? `${minutes}m` : ''}${seconds > 0 ? `${seconds}s` : ''}`;
     }
-    
-    return activities;
+    if (minutes > 0) {
+      return `${minutes}m${seconds > 0 ? `${seconds}s` : ''}`;
+    }
+    return `${seconds}s`;
   };
 
+  // Activities are parsed by the backend; convert them for display
+  const toParsedActivities = (activities: Activity[]): ParsedActivity[] =>
+    activities.map((activity) => ({
+      start: activity.start_time ? activity.start_time.replace('T', ' ').slice(0, 16) : '',
+      description: activity.description,
+      project: activity.project,
+      duration: formatDuration(activity.duration_seconds),
+    }));
+
   const refreshCurrentActivities = async () => {
-    const result = await tockCommands.getCurrentActivity();
-    console.log(`Loaded current activities:`, result);
-    const output = result.success ? result.output : result.error || 'No active activities';
-    setParsedActivities(parseActivityOutput(output));
+    try {
+      const activities = await tockCommands.getCurrentActivityStructured();
+      console.log(`Loaded current activities:`, activities);
+      setParsedActivities(toParsedActivities(activities));
+    } catch (e) {
+      console.error('Failed to load current activities:', e);
+      setParsedActivities([]);
+    }
   };
 
   const loadRecentProjects = async () => {
-    const result = await tockCommands.getRecentActivities(100);
-    if (!result.success) {
+    let activities: ParsedActivity[];
+    try {
+      activities = toParsedActivities(await tockCommands.getRecentActivitiesStructured(100));
+    } catch (e) {
+      console.error('Failed to load recent activities:', e);
       return;
     }
-
-    const activities = parseActivityOutput(result.output);
     
     // Create a map to store unique projects with their most recent description
     // Activities are returned from most recent to oldest, so we process them in reverse
@@ -160,8 +126,7 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
     
     // Check which projects are favorites
     for (const project of projects) {
-      const isFavResult = await tockCommands.isFavorite(project.name, project.description);
-      project.isFavorite = isFavResult.success && isFavResult.output === 'true';
+      project.isFavorite = await tockCommands.isFavorite(project.name, project.description).catch(() => false);
     }
     
     // Sort by name
@@ -171,32 +136,26 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
   };
 
   const loadFavorites = async () => {
-    const result = await tockCommands.getAllFavorites();
-    if (result.success) {
-      try {
-        const favs: FavoriteProject[] = JSON.parse(result.output);
-        const projects: Project[] = favs.map(f => ({
-          name: f.name,
-          description: f.description,
-          isFavorite: true,
-          source: 'favorite'
-        }));
-        setFavorites(projects);
-      } catch (e) {
-        console.error('Failed to parse favorites:', e);
-      }
+    try {
+      const favs: FavoriteProject[] = await tockCommands.getAllFavorites();
+      const projects: Project[] = favs.map(f => ({
+        name: f.name,
+        description: f.description,
+        isFavorite: true,
+        source: 'favorite'
+      }));
+      setFavorites(projects);
+    } catch (e) {
+      console.error('Failed to load favorites:', e);
     }
   };
 
   const loadApiRoutes = async () => {
-    const result = await tockCommands.getAllApiRoutes();
-    if (result.success) {
-      try {
-        const routes: ApiRoute[] = JSON.parse(result.output);
-        setApiRoutes(routes);
-      } catch (e) {
-        console.error('Failed to parse API routes:', e);
-      }
+    try {
+      const routes: ApiRoute[] = await tockCommands.getAllApiRoutes();
+      setApiRoutes(routes);
+    } catch (e) {
+      console.error('Failed to load API routes:', e);
     }
   };
 
@@ -206,8 +165,7 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
       return;
     }
     
-    const result = await tockCommands.isFavorite(project, description);
-    setCurrentIsFavorite(result.success && result.output === 'true');
+    setCurrentIsFavorite(await tockCommands.isFavorite(project, description).catch(() => false));
   };
 
   const handleFetchFromApi = async (routeId: number): Promise<Project[]> => {
@@ -215,28 +173,23 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
     if (!route) return [];
 
     try {
-      const result = await tockCommands.fetchProjectsFromApi(route.url);
-      if (result.success) {
-        // Parse the JSON response from the API
-        const parsed = JSON.parse(result.output);
-        
-        // Support both formats:
-        // 1. Direct array: [{"name": "project1", "description": "desc1"}]
-        // 2. Object with results: {results: [{"name": "project1", "description": "desc1"}]}
-        const apiProjects = Array.isArray(parsed) ? parsed : (parsed.results || []);
-        
-        // Convert to our Project format
-        return apiProjects.map((p: any) => ({
-          name: p.name || p.project,
-          description: p.description || p.desc || '',
-          source: route.name,
-        }));
-      } else {
-        showMessage('error', `Failed to fetch from ${route.name}: ${result.error}`);
-        return [];
-      }
+      const response = await tockCommands.fetchProjectsFromApi(route.url, route.id);
+      // Parse the JSON response from the API
+      const parsed = JSON.parse(response);
+      
+      // Support both formats:
+      // 1. Direct array: [{"name": "project1", "description": "desc1"}]
+      // 2. Object with results: {results: [{"name": "project1", "description": "desc1"}]}
+      const apiProjects = Array.isArray(parsed) ? parsed : (parsed.results || []);
+      
+      // Convert to our Project format
+      return apiProjects.map((p: any) => ({
+        name: p.name || p.project,
+        description: p.description || p.desc || '',
+        source: route.name,
+      }));
     } catch (e) {
-      showMessage('error', `Error fetching from ${route.name}: ${e}`);
+      showMessage('error', `Failed to fetch from ${route.name}: ${errorMessage(e)}`);
       return [];
     }
   };
@@ -273,52 +226,52 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
       return;
     }
 
-    setLoading(true);
-    let result;
     const isAdding = isAddingActivity(); // Store before clearing state
 
-    if (isAdding) {
-      // Add past activity
-      if (!startTime) {
-        showMessage('error', 'Start time is required for adding activities');
-        setLoading(false);
-        return;
-      }
-      result = await tockCommands.addActivity(
-        project,
-        description,
-        startTime,
-        endTime.trim() === '' ? undefined : endTime,
-        duration.trim() === '' ? undefined : duration
-      );
-    } else {
-      // Start new activity
-      result = await tockCommands.startActivity(
-        project,
-        description,
-        startTime.trim() === '' ? undefined : startTime
-      );
+    if (isAdding && !startTime) {
+      showMessage('error', 'Start time is required for adding activities');
+      return;
     }
 
-    setLoading(false);
-
-    if (result.success) {
-      // Handle favorites if checkbox is checked
-      if (currentIsFavorite) {
-        await tockCommands.addFavorite(project, description);
-        await loadFavorites();
+    setLoading(true);
+    try {
+      if (isAdding) {
+        // Add past activity
+        await tockCommands.addActivity(
+          project,
+          description,
+          startTime,
+          endTime.trim() === '' ? null : endTime,
+          duration.trim() === '' ? null : duration
+        );
+      } else {
+        // Start new activity
+        await tockCommands.startActivity(
+          project,
+          description,
+          startTime.trim() === '' ? null : startTime
+        );
       }
-      
-      showMessage('success', isAdding ? 'Activity added successfully!' : 'Activity started successfully!');
-      setProject('');
-      setDescription('');
-      setStartTime('');
-      setEndTime('');
-      setDuration('');
-      refreshCurrentActivities();
-    } else {
-      showMessage('error', result.error || 'Operation failed');
+    } catch (e) {
+      showMessage('error', errorMessage(e));
+      return;
+    } finally {
+      setLoading(false);
     }
+
+    // Handle favorites if checkbox is checked
+    if (currentIsFavorite) {
+      await tockCommands.addFavorite(project, description);
+      await loadFavorites();
+    }
+    
+    showMessage('success', isAdding ? 'Activity added successfully!' : 'Activity started successfully!');
+    setProject('');
+    setDescription('');
+    setStartTime('');
+    setEndTime('');
+    setDuration('');
+    refreshCurrentActivities();
   };
 
   const toggleFavorite = async () => {
@@ -347,15 +300,15 @@ export const ActivityTab: React.FC<ActivityTabProps> = ({ showMessage }) => {
 
   const handleStopActivity = async () => {
     setLoading(true);
-    const result = await tockCommands.stopActivity(endTime.trim() === '' ? undefined : endTime);
-    setLoading(false);
-
-    if (result.success) {
+    try {
+      await tockCommands.stopActivity(endTime.trim() === '' ? null : endTime);
       showMessage('success', 'Activity stopped successfully!');
       setEndTime('');
       refreshCurrentActivities();
-    } else {
-      showMessage('error', result.error || 'Failed to stop activity');
+    } catch (e) {
+      showMessage('error', `Failed to stop activity: ${errorMessage(e)}`);
+    } finally {
+      setLoading(false);
     }
   };
 
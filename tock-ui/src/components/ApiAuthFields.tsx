@@ -0,0 +1,95 @@
+import React from 'react';
+import { ApiAuth } from '../types';
+
+interface ApiAuthFieldsProps {
+  auth: ApiAuth;
+  onChange: (auth: ApiAuth) => void;
+}
+
+const inputClass =
+  'w-full px-3 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500';
+
+// Empty credentials for a newly selected auth type
+const emptyAuth = (type: ApiAuth['type']): ApiAuth => {
+  switch (type) {
+    case 'bearer':
+      return { type, token: '' };
+    case 'basic':
+      return { type, username: '', password: '' };
+    case 'header':
+      return { type, name: '', value: '' };
+    case 'query_key':
+      return { type, name: '', value: '' };
+    default:
+      return { type: 'none' };
+  }
+};
+
+export const ApiAuthFields: React.FC<ApiAuthFieldsProps> = ({ auth, onChange }) => {
+  return (
+    <div className="space-y-2">
+      <label className="block text-sm font-medium text-slate-700 mb-1">
+        Authentication
+      </label>
+      <select
+        value={auth.type}
+        onChange={(e) => onChange(emptyAuth(e.target.value as ApiAuth['type']))}
+        className={inputClass}
+      >
+        <option value="none">None</option>
+        <option value="bearer">Bearer token</option>
+        <option value="basic">Basic auth</option>
+        <option value="header">Custom header</option>
+        <option value="query_key">API key in query string</option>
+      </select>
+
+      {auth.type === 'bearer' && (
+        <input
+          type="password"
+          value={auth.token}
+          onChange={(e) => onChange({ ...auth, token: e.target.value })}
+          className={inputClass}
+          placeholder="Token"
+        />
+      )}
+
+      {auth.type === 'basic' && (
+        <>
+          <input
+            type="text"
+            value={auth.username}
+            onChange={(e) => onChange({ ...auth, username: e.target.value })}
+            className={inputClass}
+            placeholder="Username"
+          />
+          <input
+            type="password"
+            value={auth.password}
+            onChange={(e) => onChange({ ...auth, password: e.target.value })}
+            className={inputClass}
+            placeholder="Password"
+          />
+        </>
+      )}
+
+      {(auth.type === 'header' || auth.type === 'query_key') && (
+        <>
+          <input
+            type="text"
+            value={auth.name}
+            onChange={(e) => onChange({ ...auth, name: e.target.value })}
+            className={inputClass}
+            placeholder={auth.type === 'header' ? 'Header name, e.g. X-API-Key' : 'Parameter name, e.g. api_key'}
+          />
+          <input
+            type="password"
+            value={auth.value}
+            onChange={(e) => onChange({ ...auth, value: e.target.value })}
+            className={inputClass}
+            placeholder="Value"
+          />
+        </>
+      )}
+    </div>
+  );
+};
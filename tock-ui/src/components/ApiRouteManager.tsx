@@ -1,7 +1,8 @@
 import React, { useState, useEffect } from 'react';
-import { tockCommands } from '../api';
+import { errorMessage, tockCommands } from '../api';
 import { Plus, Trash2, Edit2, X, Check, AlertCircle } from 'lucide-react';
-import { ApiRoute } from '../types';
+import { ApiAuthFields } from './ApiAuthFields';
+import { ApiAuth, ApiRoute } from '../types';
 
 interface ApiRouteManagerProps {
   showMessage: (type: 'success' | 'error', text: string) => void;
@@ -11,22 +12,27 @@ export const ApiRouteManager: React.FC<ApiRouteManagerProps> = ({ showMessage })
   const [routes, setRoutes] = useState<ApiRoute[]>([]);
   const [isAdding, setIsAdding] = useState(false);
   const [editingId, setEditingId] = useState<number | null>(null);
-  const [newRoute, setNewRoute] = useState({ name: '', url: '' });
-  const [editRoute, setEditRoute] = useState({ name: '', url: '', enabled: true });
+  const [newRoute, setNewRoute] = useState<{ name: string; url: string; auth: ApiAuth }>({
+    name: '',
+    url: '',
+    auth: { type: 'none' },
+  });
+  const [editRoute, setEditRoute] = useState<{ name: string; url: string; enabled: boolean; auth: ApiAuth }>({
+    name: '',
+    url: '',
+    enabled: true,
+    auth: { type: 'none' },
+  });
 
   useEffect(() => {
     loadRoutes();
   }, []);
 
   const loadRoutes = async () => {
-    const result = await tockCommands.getAllApiRoutes();
-    if (result.success) {
-      try {
-        const parsedRoutes: ApiRoute[] = JSON.parse(result.output);
-        setRoutes(parsedRoutes);
-      } catch (e) {
-        console.error('Failed to parse routes:', e);
-      }
+    try {
+      setRoutes(await tockCommands.getAllApiRoutes());
+    } catch (e) {
+      console.error('Failed to load routes:', e);
     }
   };
 
@@ -36,30 +42,31 @@ export const ApiRouteManager: React.FC<ApiRouteManagerProps> = ({ showMessage })
       return;
     }
 
-    const result = await tockCommands.addApiRoute(newRoute.name, newRoute.url);
-    if (result.success) {
+    try {
+      await tockCommands.addApiRoute(newRoute.name, newRoute.url, newRoute.auth);
       showMessage('success', 'API route added successfully');
-      setNewRoute({ name: '', url: '' });
+      setNewRoute({ name: '', url: '', auth: { type: 'none' } });
       setIsAdding(false);
       loadRoutes();
-    } else {
-      showMessage('error', result.error || 'Failed to add route');
+    } catch (e) {
+      showMessage('error', `Failed to add route: ${errorMessage(e)}`);
     }
   };
 
   const handleUpdate = async (id: number) => {
-    const result = await tockCommands.updateApiRoute(
-      id,
-      editRoute.name,
-      editRoute.url,
-      editRoute.enabled
-    );
-    if (result.success) {
+    try {
+      await tockCommands.updateApiRoute(
+        id,
+        editRoute.name,
+        editRoute.url,
+        editRoute.enabled,
+        editRoute.auth
+      );
       showMessage('success', 'API route updated successfully');
       setEditingId(null);
       loadRoutes();
-    } else {
-      showMessage('error', result.error || 'Failed to update route');
+    } catch (e) {
+      showMessage('error', `Failed to update route: ${errorMessage(e)}`);
     }
   };
 
@@ -67,35 +74,44 @@ export const ApiRouteManager: React.FC<ApiRouteManagerProps> = ({ showMessage })
     if (!confirm('Are you sure you want to delete this API route? This will also delete all cached projects from this API.')) return;
 
     // Delete cached projects first
-    const deleteCacheResult = await tockCommands.deleteCachedProjectsByApi(id);
-    if (!deleteCacheResult.success) {
-      console.warn('Failed to delete cached projects:', deleteCacheResult.error);
+    try {
+      await tockCommands.deleteCachedProjectsByApi(id);
+    } catch (e) {
+      console.warn('Failed to delete cached projects:', errorMessage(e));
     }
 
-    const result = await tockCommands.deleteApiRoute(id);
-    if (result.success) {
+    try {
+      await tockCommands.deleteApiRoute(id);
       showMessage('success', 'API route and cached projects deleted successfully');
       loadRoutes();
-    } else {
-      showMessage('error', result.error || 'Failed to delete route');
+    } catch (e) {
+      showMessage('error', `Failed to delete route: ${errorMessage(e)}`);
     }
   };
 
   const handleToggleEnabled = async (route: ApiRoute) => {
-    const result = await tockCommands.updateApiRoute(
-      route.id!,
-      route.name,
-      route.url,
-      !route.enabled
-    );
-    if (result.success) {
+    try {
+      await tockCommands.updateApiRoute(
+        route.id!,
+        route.name,
+        route.url,
+        !route.enabled,
+        null
+      );
       loadRoutes();
+    } catch (e) {
+      showMessage('error', `Failed to update route: ${errorMessage(e)}`);
     }
   };
 
   const startEdit = (route: ApiRoute) => {
     setEditingId(route.id!);
-    setEditRoute({ name: route.name, url: route.url, enabled: route.enabled });
+    setEditRoute({
+      name: route.name,
+      url: route.url,
+      enabled: route.enabled,
+      auth: route.auth ?? { type: 'none' },
+    });
   };
 
   return (
@@ -169,6 +185,10 @@ Format 2 (Object with results):
                 placeholder="https://api.example.com/projects"
               />
             </div>
+            <ApiAuthFields
+              auth={newRoute.auth}
+              onChange={(auth) => setNewRoute({ ...newRoute, auth })}
+            />
             <button
               onClick={handleAdd}
               className="w-full px-4 py-2 bg-slate-700 text-white rounded-lg hover:bg-slate-800 transition-colors"
@@ -205,6 +225,10 @@ Format 2 (Object with results):
                     onChange={(e) => setEditRoute({ ...editRoute, url: e.target.value })}
                     className="w-full px-3 py-2 border border-slate-300 rounded-lg"
                   />
+                  <ApiAuthFields
+                    auth={editRoute.auth}
+                    onChange={(auth) => setEditRoute({ ...editRoute, auth })}
+                  />
                   <div className="flex gap-2">
                     <button
                       onClick={() => handleUpdate(route.id!)}
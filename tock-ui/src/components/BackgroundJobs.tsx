@@ -0,0 +1,67 @@
+import React, { useState, useEffect } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { tockCommands } from '../api';
+import { JobState } from '../types';
+
+const JOB_LABELS: Record<string, string> = {
+  auto_send: 'Automatic report sending',
+  project_sync: 'API project sync',
+  cache_warm: 'Calendar cache warming',
+  backup: 'Database backup',
+};
+
+export const BackgroundJobs: React.FC = () => {
+  const [jobs, setJobs] = useState<JobState[]>([]);
+
+  useEffect(() => {
+    loadJobs();
+
+    // Refresh whenever the backend scheduler finishes a job
+    const unlisten = listen<JobState>('scheduler-job-finished', () => {
+      loadJobs();
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const loadJobs = async () => {
+    try {
+      setJobs(await tockCommands.getSchedulerJobs());
+    } catch (e) {
+      console.error('Failed to load scheduler jobs:', e);
+    }
+  };
+
+  if (jobs.length === 0) return null;
+
+  return (
+    <div className="bg-slate-50 rounded-lg p-6 border border-slate-200 mt-6">
+      <h2 className="text-xl font-semibold text-slate-800 mb-4">Background Jobs</h2>
+      <div className="space-y-2">
+        {jobs.map((job) => (
+          <div key={job.name} className="bg-white p-3 rounded-lg border border-slate-200">
+            <div className="flex items-center justify-between">
+              <span className="text-sm font-medium text-slate-800">{JOB_LABELS[job.name] ?? job.name}</span>
+              <span
+                className={`px-2 py-0.5 rounded text-xs ${
+                  job.last_success ? 'bg-green-100 text-green-800' : 'bg-red-100 text-red-800'
+                }`}
+              >
+                {job.last_success ? 'OK' : 'Failed'}
+              </span>
+            </div>
+            <p className="text-xs text-slate-500 mt-1">
+              {job.last_run_at && `Last run ${new Date(job.last_run_at).toLocaleString()}`}
+              {job.next_run_at && ` · next ${new Date(job.next_run_at).toLocaleString()}`}
+            </p>
+            {job.last_message && (
+              <p className="text-xs text-slate-600 truncate" title={job.last_message}>{job.last_message}</p>
+            )}
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
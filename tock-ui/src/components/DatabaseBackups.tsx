@@ -0,0 +1,83 @@
+import React, { useState, useEffect } from 'react';
+import { Archive, RotateCcw } from 'lucide-react';
+import { errorMessage, tockCommands } from '../api';
+import { BackupInfo } from '../types';
+
+interface DatabaseBackupsProps {
+  showMessage: (type: 'success' | 'error', text: string) => void;
+}
+
+export const DatabaseBackups: React.FC<DatabaseBackupsProps> = ({ showMessage }) => {
+  const [backups, setBackups] = useState<BackupInfo[]>([]);
+
+  useEffect(() => {
+    loadBackups();
+  }, []);
+
+  const loadBackups = async () => {
+    try {
+      setBackups(await tockCommands.listBackups());
+    } catch (e) {
+      console.error('Failed to load backups:', e);
+    }
+  };
+
+  const handleCreate = async () => {
+    try {
+      showMessage('success', await tockCommands.createBackup());
+      loadBackups();
+    } catch (e) {
+      showMessage('error', `Failed to create backup: ${errorMessage(e)}`);
+    }
+  };
+
+  const handleRestore = async (backup: BackupInfo) => {
+    if (!confirm(`Restore the database from ${backup.file_name}? Current data is backed up first.`)) return;
+
+    try {
+      showMessage('success', await tockCommands.restoreBackup(backup.file_name));
+      loadBackups();
+    } catch (e) {
+      showMessage('error', `Failed to restore backup: ${errorMessage(e)}`);
+    }
+  };
+
+  return (
+    <div className="bg-slate-50 rounded-lg p-6 border border-slate-200 mt-6">
+      <div className="flex items-center justify-between mb-4">
+        <h2 className="text-xl font-semibold text-slate-800">Database Backups</h2>
+        <button
+          onClick={handleCreate}
+          className="px-4 py-2 bg-slate-600 text-white rounded-lg hover:bg-slate-700 transition-colors flex items-center gap-2"
+        >
+          <Archive size={18} />
+          Back Up Now
+        </button>
+      </div>
+
+      {backups.length === 0 ? (
+        <p className="text-sm text-slate-500">No backups yet. A backup is taken daily and before database upgrades.</p>
+      ) : (
+        <div className="space-y-2">
+          {backups.map((backup) => (
+            <div key={backup.file_name} className="bg-white p-3 rounded-lg border border-slate-200 flex items-center justify-between">
+              <div>
+                <p className="text-sm font-mono text-slate-800">{backup.file_name}</p>
+                <p className="text-xs text-slate-500">
+                  {new Date(backup.created_at).toLocaleString()} · {(backup.size_bytes / 1024).toFixed(0)} KB
+                </p>
+              </div>
+              <button
+                onClick={() => handleRestore(backup)}
+                className="p-2 text-slate-600 hover:bg-slate-100 rounded-lg transition-colors"
+                title="Restore this backup"
+              >
+                <RotateCcw size={18} />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
@@ -1,5 +1,6 @@
-import React, { useState, useEffect, useMemo, useCallback } from 'react';
-import { tockCommands } from '../api';
+import React, { useState, useEffect, useMemo, useCallback, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { errorMessage, isAppError, tockCommands } from '../api';
 import { ChevronLeft, ChevronRight, Star } from 'lucide-react';
 import { 
   startOfMonth, 
@@ -11,13 +12,21 @@ import {
   isSameDay,
   getDay
 } from 'date-fns';
+import { Activity, MonthLoadProgress, Settings, TockDataChanged } from '../types';
+
+// Indexed like date-fns getDay(): 0 is Sunday
+const WEEKDAY_NAMES = ['sunday', 'monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday'];
+const WEEKDAY_LABELS = ['Sun', 'Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat'];
 
 interface ActivityData {
   date: string;
-  activities: string[];
+  activities: Activity[];
   colors: { [project: string]: string };
 }
 
+// "13:46" from a backend timestamp such as "2026-01-01T13:46:00"
+const timeOfDay = (timestamp: string) => timestamp.slice(11, 16);
+
 // Memoized CalendarDay component to prevent unnecessary re-renders
 interface CalendarDayProps {
   day: Date;
@@ -76,10 +85,10 @@ export const HistoryTab: React.FC = () => {
   const [activitiesData, setActivitiesData] = useState<{ [key: string]: ActivityData }>({});
   const [loading, setLoading] = useState(false);
   const [favorites, setFavorites] = useState<Set<string>>(new Set());
-
-  // Date separator format used by the backend
-  // Format: === YYYY-MM-DD ===
-  const DATE_SEPARATOR_REGEX = /\n*===\s*(\d{4}-\d{2}-\d{2})\s*===\n*/;
+  const [weekStartIndex, setWeekStartIndex] = useState(0);
+  // Operation id of the month load in progress, used to cancel it
+  const monthLoadRef = useRef<string | null>(null);
+  const [loadProgress, setLoadProgress] = useState<{ completed: number; total: number } | null>(null);
 
   // Color palette for activities
   const colorPalette = useMemo(() => [
@@ -93,54 +102,10 @@ export const HistoryTab: React.FC = () => {
     '#22d3ee', // cyan-400
   ], []);
 
-  const parseActivitiesOutput = useCallback((output: string, dateStr: string): ActivityData => {
-    const lines = output.trim().split('\n');
-    const activities: string[] = [];
+  const toActivityData = useCallback((activities: Activity[], dateStr: string): ActivityData => {
     const colors: { [project: string]: string } = {};
-    const projects = new Set<string>();
-    
-    // Skip the header lines
-    let currentProject = '';
-    
-    lines.forEach((line) => {
-      const trimmedLine = line.trim();
-      
-      if (!trimmedLine || trimmedLine.includes('Time Tracking Report') || trimmedLine.includes('=====')) {
-        return; // Skip empty lines and headers
-      }
-      
-      // Check if it's a project header (starts with 📁)
-      if (trimmedLine.startsWith('📁')) {
-        // Extract project name from "📁 loiu: 1h 3m"
-        const projectMatch = trimmedLine.match(/📁\s*([^:]+):/);
-        if (projectMatch) {
-          currentProject = projectMatch[1].trim();
-          projects.add(currentProject);
-        }
-        return;
-      }
-      
-      // Check if it's an activity line (contains timestamp)
-      if (trimmedLine.includes('-') && trimmedLine.includes('(') && currentProject) {
-        // The line format is: "13:46 - 14:49 (1h 3m) | uil"
-        // We want to extract the description after the pipe
-        const parts = trimmedLine.split('|');
-        if (parts.length >= 2) {
-          const timePart = parts[0].trim(); // "13:46 - 14:49 (1h 3m)"
-          const description = parts[1].trim(); // "uil"
-          
-          // Format as: "TIME | PROJECT | DESCRIPTION"
-          const formattedActivity = `${timePart} | ${currentProject} | ${description}`;
-          activities.push(formattedActivity);
-        } else {
-          // Fallback: just add the line as-is
-          activities.push(trimmedLine);
-        }
-      }
-    });
-
-    // Assign colors to projects
-    Array.from(projects).forEach((project, index) => {
+    const projects = Array.from(new Set(activities.map((activity) => activity.project)));
+    projects.forEach((project, index) => {
       colors[project] = colorPalette[index % colorPalette.length];
     });
 
@@ -153,70 +118,101 @@ export const HistoryTab: React.FC = () => {
     const month = currentMonth.getMonth() + 1; // JavaScript months are 0-indexed
     const yearMonth = `${year}-${String(month).padStart(2, '0')}`;
 
-    // Try to load from cache first if useCache is true
-    if (useCache) {
-      const cacheResult = await tockCommands.getCalendarCache(yearMonth);
-      if (cacheResult.success) {
-        try {
-          const cachedData = JSON.parse(cacheResult.output);
-          setActivitiesData(cachedData);
-          setLoading(false);
-          return;
-        } catch (e) {
-          console.error('Failed to parse calendar cache:', e);
-          // Continue to fetch fresh data
-        }
+    // The backend caches months, a refresh asks tock again
+    if (!useCache) {
+      try {
+        await tockCommands.clearCalendarCache(yearMonth);
+      } catch (e) {
+        console.error('Failed to clear calendar cache:', e);
       }
     }
 
-    const newActivitiesData: { [key: string]: ActivityData } = {};
-
-    // Use the new bulk month fetch API
-    const result = await tockCommands.getActivitiesForMonth(year, month);
-    
-    if (result.success && result.output.trim()) {
-      // Parse the combined output which has format defined by DATE_SEPARATOR_REGEX:
-      // === 2026-01-01 ===
-      // <report data>
-      //
-      // === 2026-01-02 ===
-      // <report data>
-      
-      const sections = result.output.split(DATE_SEPARATOR_REGEX);
-      
-      // sections will be like: ['', '2026-01-01', '<data>', '2026-01-02', '<data>', ...]
-      // Ensure we have pairs of (date, data) by checking bounds
-      for (let i = 1; i + 1 < sections.length; i += 2) {
-        const dateStr = sections[i];
-        const output = sections[i + 1];
-        
-        if (dateStr && output && output.trim()) {
-          newActivitiesData[dateStr] = parseActivitiesOutput(output, dateStr);
+    // Stop a load that is still running, its result would be replaced anyway
+    if (monthLoadRef.current) {
+      tockCommands.cancelOperation(monthLoadRef.current).catch(() => {});
+    }
+    const operationId = `month-load-${yearMonth}-${Date.now()}`;
+    monthLoadRef.current = operationId;
+    setLoadProgress(null);
+
+    let activities: Activity[] = [];
+    try {
+      activities = await tockCommands.getActivitiesForMonthStructured(year, month, operationId);
+    } catch (e) {
+      if (isAppError(e) && e.code === 'cancelled') {
+        // Either a newer load took over, which resets loading itself, or the user stopped this one
+        if (monthLoadRef.current === operationId) {
+          monthLoadRef.current = null;
+          setLoadProgress(null);
+          setLoading(false);
         }
+        return;
       }
-      
-      // Save to cache
-      await tockCommands.saveCalendarCache(yearMonth, JSON.stringify(newActivitiesData));
+      console.error('Failed to load activities for month:', errorMessage(e));
+    }
+    if (monthLoadRef.current === operationId) {
+      monthLoadRef.current = null;
     }
+    setLoadProgress(null);
+
+    // Group by the day each activity started on
+    const byDay: { [date: string]: Activity[] } = {};
+    activities.forEach((activity) => {
+      if (activity.start_time) {
+        const dateStr = activity.start_time.slice(0, 10);
+        byDay[dateStr] = [...(byDay[dateStr] ?? []), activity];
+      }
+    });
+
+    const newActivitiesData: { [key: string]: ActivityData } = {};
+    Object.entries(byDay).forEach(([dateStr, dayActivities]) => {
+      newActivitiesData[dateStr] = toActivityData(dayActivities, dateStr);
+    });
 
     setActivitiesData(newActivitiesData);
     setLoading(false);
-  }, [parseActivitiesOutput, currentMonth]);
+  }, [toActivityData, currentMonth]);
   
   const handleRefresh = useCallback(async () => {
     await loadActivitiesForMonth(false); // Force refresh, bypass cache
   }, [loadActivitiesForMonth]);
 
-  const loadFavorites = useCallback(async () => {
-    const result = await tockCommands.getAllFavorites();
-    if (result.success) {
-      try {
-        const favs = JSON.parse(result.output);
-        const favSet = new Set<string>(favs.map((f: any) => `${f.name}|${f.description}`));
-        setFavorites(favSet);
-      } catch (e) {
-        console.error('Failed to parse favorites:', e);
+  const cancelMonthLoad = useCallback(() => {
+    if (monthLoadRef.current) {
+      tockCommands.cancelOperation(monthLoadRef.current).catch((e) => {
+        console.error('Failed to cancel loading activities:', errorMessage(e));
+      });
+    }
+  }, []);
+
+  // Don't leave tock running for a calendar that is no longer shown
+  useEffect(() => cancelMonthLoad, [cancelMonthLoad]);
+
+  // Fill in days as the backend finishes them, the full result replaces them at the end
+  useEffect(() => {
+    const unlisten = listen<MonthLoadProgress>('month-load-progress', (event) => {
+      const { operation_id, date, completed, total, activities } = event.payload;
+      if (operation_id === null || operation_id !== monthLoadRef.current) {
+        return;
       }
+      setLoadProgress({ completed, total });
+      if (activities.length > 0) {
+        setActivitiesData((prev) => ({ ...prev, [date]: toActivityData(activities, date) }));
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [toActivityData]);
+
+  const loadFavorites = useCallback(async () => {
+    try {
+      const favs = await tockCommands.getAllFavorites();
+      const favSet = new Set<string>(favs.map((f) => `${f.name}|${f.description}`));
+      setFavorites(favSet);
+    } catch (e) {
+      console.error('Failed to load favorites:', e);
     }
   }, []);
 
@@ -239,12 +235,43 @@ export const HistoryTab: React.FC = () => {
     }
   }, [favorites]);
 
+  useEffect(() => {
+    loadWeekStart();
+  }, []);
+
+  const loadWeekStart = async () => {
+    try {
+      const settings: Settings = await tockCommands.getSettings();
+      const index = WEEKDAY_NAMES.indexOf(settings.week_start);
+      if (index >= 0) setWeekStartIndex(index);
+    } catch (e) {
+      console.error('Failed to load settings:', e);
+    }
+  };
+
   useEffect(() => {
     loadActivitiesForMonth();
     loadFavorites();
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [currentMonth]); // Only re-run when currentMonth changes
 
+  // Reload the shown month when tock is used outside the app and changes it
+  useEffect(() => {
+    const yearMonth = format(currentMonth, 'yyyy-MM');
+    const unlisten = listen<TockDataChanged>('tock-data-changed', (event) => {
+      const { start_date, end_date } = event.payload;
+      const affected = start_date === null || end_date === null
+        || (start_date.slice(0, 7) <= yearMonth && yearMonth <= end_date.slice(0, 7));
+      if (affected) {
+        loadActivitiesForMonth(false);
+      }
+    });
+
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [currentMonth, loadActivitiesForMonth]);
+
   const handleDateClick = useCallback((date: Date) => {
     setSelectedDate(date);
   }, []);
@@ -268,35 +295,19 @@ export const HistoryTab: React.FC = () => {
     let totalMinutes = 0;
 
     dayData.activities.forEach((activity) => {
-      const parts = activity.split('|');
-      if (parts.length >= 3) {
-        const timePart = parts[0].trim();
-        const projectPart = parts[1].trim();
-        const descPart = parts[2].trim();
-
-        // Extract time from "2026-01-01 13:46 - 13:47 (0h 1m)"
-        const timeMatch = timePart.match(/(\d{2}:\d{2})\s*-\s*(\d{2}:\d{2})/);
-        const displayTime = timeMatch ? timeMatch[0] : '';
-
-        // Parse duration
-        const durationMatch = activity.match(/\((\d+)h\s*(\d+)m\)/);
-        let minutes = 0;
-        if (durationMatch) {
-          minutes = parseInt(durationMatch[1], 10) * 60 + parseInt(durationMatch[2], 10);
-        }
+      const start = activity.start_time ? timeOfDay(activity.start_time) : '';
+      const end = activity.end_time ? timeOfDay(activity.end_time) : 'now';
+      const minutes = Math.floor((activity.duration_seconds ?? 0) / 60);
+
+      timelineItems.push({
+        time: start ? `${start} - ${end}` : '',
+        project: activity.project,
+        description: activity.description,
+        color: dayData.colors[activity.project] || '#cbd5e1',
+        duration: minutes,
+      });
 
-        const color = dayData.colors[projectPart] || '#cbd5e1';
-        
-        timelineItems.push({
-          time: displayTime,
-          project: projectPart,
-          description: descPart,
-          color: color,
-          duration: minutes,
-        });
-
-        totalMinutes += minutes;
-      }
+      totalMinutes += minutes;
     });
 
     const totalHours = Math.floor(totalMinutes / 60);
@@ -312,12 +323,13 @@ export const HistoryTab: React.FC = () => {
     return eachDayOfInterval({ start: monthStart, end: monthEnd });
   }, [currentMonth]);
 
+  // Empty cells before the first day, counted from the configured week start
   const firstDayOfWeek = useMemo(() => {
-    return getDay(startOfMonth(currentMonth));
-  }, [currentMonth]);
+    return (getDay(startOfMonth(currentMonth)) - weekStartIndex + 7) % 7;
+  }, [currentMonth, weekStartIndex]);
 
   const renderCalendar = () => {
-    const weekDays = ['Sun', 'Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat'];
+    const weekDays = [...WEEKDAY_LABELS.slice(weekStartIndex), ...WEEKDAY_LABELS.slice(0, weekStartIndex)];
 
     return (
       <div className="bg-white rounded-lg p-4 border border-slate-200">
@@ -391,7 +403,13 @@ export const HistoryTab: React.FC = () => {
 
         {loading && (
           <div className="text-center mt-4 text-slate-600">
-            Loading activities...
+            Loading activities{loadProgress ? ` (${loadProgress.completed}/${loadProgress.total} days)` : ''}...
+            <button
+              onClick={cancelMonthLoad}
+              className="ml-2 text-sm text-slate-500 underline hover:text-slate-700"
+            >
+              Cancel
+            </button>
           </div>
         )}
       </div>
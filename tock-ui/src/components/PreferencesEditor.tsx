@@ -0,0 +1,211 @@
+import React, { useState, useEffect } from 'react';
+import { errorMessage, tockCommands } from '../api';
+import { ReportFormat, RoundingMode, Settings, Theme } from '../types';
+
+interface PreferencesEditorProps {
+  settings: Settings;
+  showMessage: (type: 'success' | 'error', text: string) => void;
+  onSaved: (settings: Settings) => void;
+}
+
+const WEEKDAYS = ['monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday'];
+
+const inputClass = 'w-full px-3 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500';
+
+const capitalize = (value: string) => value.charAt(0).toUpperCase() + value.slice(1);
+
+// Environment overrides are edited as one KEY=value per line
+const formatEnv = (env: Settings['tock_env']) =>
+  Object.entries(env).map(([name, value]) => `${name}=${value ?? ''}`).join('\n');
+
+const parseEnv = (text: string): Settings['tock_env'] =>
+  Object.fromEntries(
+    text
+      .split('\n')
+      .map((line) => line.trim())
+      .filter((line) => line && !line.startsWith('#'))
+      .map((line) => {
+        const separator = line.indexOf('=');
+        return separator < 0 ? [line, ''] : [line.slice(0, separator).trim(), line.slice(separator + 1)];
+      })
+  );
+
+export const PreferencesEditor: React.FC<PreferencesEditorProps> = ({ settings, showMessage, onSaved }) => {
+  const [draft, setDraft] = useState<Settings>(settings);
+  const [backends, setBackends] = useState<string[]>([settings.tracker_backend]);
+  const [envText, setEnvText] = useState(formatEnv(settings.tock_env));
+  const [detectedTockPath, setDetectedTockPath] = useState<string | null>(null);
+
+  useEffect(() => {
+    setDraft(settings);
+    setEnvText(formatEnv(settings.tock_env));
+  }, [settings]);
+
+  useEffect(() => {
+    loadBackends();
+    loadDetectedTockPath();
+  }, []);
+
+  const loadDetectedTockPath = async () => {
+    try {
+      setDetectedTockPath(await tockCommands.detectTockPath());
+    } catch (e) {
+      console.error('Failed to look for tock:', e);
+    }
+  };
+
+  const loadBackends = async () => {
+    try {
+      setBackends((await tockCommands.getAvailableBackends()).available);
+    } catch (e) {
+      console.error('Failed to load available backends:', e);
+    }
+  };
+
+  const handleSave = async () => {
+    try {
+      onSaved(await tockCommands.updateSettings({ ...draft, tock_env: parseEnv(envText) }));
+      showMessage('success', 'Preferences saved');
+    } catch (e) {
+      showMessage('error', `Failed to save preferences: ${errorMessage(e)}`);
+    }
+  };
+
+  return (
+    <div className="bg-slate-50 rounded-lg p-6 border border-slate-200 mt-6">
+      <h2 className="text-xl font-semibold text-slate-800 mb-4">Preferences</h2>
+      <div className="grid grid-cols-2 gap-4">
+        <label className="text-sm text-slate-700">
+          Default report format
+          <select
+            value={draft.default_report_format}
+            onChange={(e) => setDraft({ ...draft, default_report_format: e.target.value as ReportFormat })}
+            className={inputClass}
+          >
+            <option value="txt">Text</option>
+            <option value="csv">CSV</option>
+            <option value="json">JSON</option>
+            <option value="markdown">Markdown</option>
+            <option value="html">HTML</option>
+          </select>
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Week starts on
+          <select
+            value={draft.week_start}
+            onChange={(e) => setDraft({ ...draft, week_start: e.target.value })}
+            className={inputClass}
+          >
+            {WEEKDAYS.map((day) => (
+              <option key={day} value={day}>{capitalize(day)}</option>
+            ))}
+          </select>
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Theme
+          <select
+            value={draft.theme}
+            onChange={(e) => setDraft({ ...draft, theme: e.target.value as Theme })}
+            className={inputClass}
+          >
+            <option value="system">System</option>
+            <option value="light">Light</option>
+            <option value="dark">Dark</option>
+          </select>
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Time tracking backend
+          <select
+            value={draft.tracker_backend}
+            onChange={(e) => setDraft({ ...draft, tracker_backend: e.target.value })}
+            className={inputClass}
+          >
+            {backends.map((backend) => (
+              <option key={backend} value={backend}>{backend}</option>
+            ))}
+          </select>
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Round report durations to (minutes, 0 = off)
+          <input
+            type="number"
+            min={0}
+            max={60}
+            value={draft.rounding.minutes}
+            onChange={(e) => setDraft({ ...draft, rounding: { ...draft.rounding, minutes: Number(e.target.value) } })}
+            className={inputClass}
+          />
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Rounding
+          <select
+            value={draft.rounding.mode}
+            onChange={(e) => setDraft({ ...draft, rounding: { ...draft.rounding, mode: e.target.value as RoundingMode } })}
+            className={inputClass}
+          >
+            <option value="nearest">To nearest</option>
+            <option value="up">Up</option>
+            <option value="down">Down</option>
+          </select>
+        </label>
+
+        <label className="col-span-2 text-sm text-slate-700">
+          Activity file (native backend)
+          <input
+            type="text"
+            value={draft.tock_file_path ?? ''}
+            onChange={(e) => setDraft({ ...draft, tock_file_path: e.target.value || null })}
+            placeholder="TOCK_FILE or ~/.tock.txt"
+            className={inputClass}
+          />
+        </label>
+
+        <label className="col-span-2 text-sm text-slate-700">
+          tock executable
+          <input
+            type="text"
+            value={draft.tock_binary_path ?? ''}
+            onChange={(e) => setDraft({ ...draft, tock_binary_path: e.target.value || null })}
+            placeholder={detectedTockPath ? `Found at ${detectedTockPath}` : 'Not found on PATH, in ~/go/bin or Homebrew'}
+            className={inputClass}
+          />
+        </label>
+
+        <label className="col-span-2 text-sm text-slate-700">
+          Environment for tock commands (one KEY=value per line)
+          <textarea
+            value={envText}
+            onChange={(e) => setEnvText(e.target.value)}
+            placeholder="TOCK_FILE=/path/to/.tock.txt"
+            rows={3}
+            className={`${inputClass} font-mono`}
+          />
+        </label>
+
+        <label className="text-sm text-slate-700">
+          Stop tock commands after (seconds)
+          <input
+            type="number"
+            min={1}
+            max={600}
+            value={draft.tock_timeout_seconds}
+            onChange={(e) => setDraft({ ...draft, tock_timeout_seconds: Number(e.target.value) })}
+            className={inputClass}
+          />
+        </label>
+      </div>
+
+      <button
+        onClick={handleSave}
+        className="mt-4 px-4 py-2 bg-slate-700 text-white rounded-lg hover:bg-slate-800 transition-colors"
+      >
+        Save Preferences
+      </button>
+    </div>
+  );
+};
@@ -1,7 +1,9 @@
 import React, { useState, useEffect } from 'react';
-import { tockCommands } from '../api';
-import { Send, CheckCircle, AlertCircle, Plus, Trash2, Edit2, X, Check } from 'lucide-react';
-import { ApiRoute, ReportSettings } from '../types';
+import { errorMessage, tockCommands } from '../api';
+import { Send, CheckCircle, AlertCircle, Plus, Trash2, Edit2, X, Check, RotateCw } from 'lucide-react';
+import { ApiAuthFields } from './ApiAuthFields';
+import { ReportScheduleEditor } from './ReportScheduleEditor';
+import { ApiAuth, ApiRoute, OutboxEntry, ReportDelivery, ReportPeriodType, ReportSettings } from '../types';
 
 interface ReportApiSenderProps {
   showMessage: (type: 'success' | 'error', text: string) => void;
@@ -9,16 +11,37 @@ interface ReportApiSenderProps {
 
 export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage }) => {
   const [routes, setRoutes] = useState<ApiRoute[]>([]);
+  const [outbox, setOutbox] = useState<OutboxEntry[]>([]);
+  const [deliveries, setDeliveries] = useState<ReportDelivery[]>([]);
+  const [showHistory, setShowHistory] = useState(false);
   const [settings, setSettings] = useState<ReportSettings>({
+    id: null,
     auto_send_enabled: false,
-    selected_api_route_id: undefined,
+    selected_api_route_id: null,
+    last_sent_at: null,
+    period_type: 'month',
+    cutoff_day: null,
+    send_partial: false,
+    last_closed_period: null,
+    schedule: { type: 'period_end' },
+    last_auto_run_at: null,
+    updated_at: '',
   });
   const [loading, setLoading] = useState(false);
   const [sending, setSending] = useState(false);
   const [isAdding, setIsAdding] = useState(false);
   const [editingId, setEditingId] = useState<number | null>(null);
-  const [newRoute, setNewRoute] = useState({ name: '', url: '' });
-  const [editRoute, setEditRoute] = useState({ name: '', url: '', enabled: true });
+  const [newRoute, setNewRoute] = useState<{ name: string; url: string; auth: ApiAuth }>({
+    name: '',
+    url: '',
+    auth: { type: 'none' },
+  });
+  const [editRoute, setEditRoute] = useState<{ name: string; url: string; enabled: boolean; auth: ApiAuth }>({
+    name: '',
+    url: '',
+    enabled: true,
+    auth: { type: 'none' },
+  });
 
   useEffect(() => {
     loadData();
@@ -28,25 +51,31 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
     setLoading(true);
     
     // Load Report API routes (separate from regular API routes)
-    const routesResult = await tockCommands.getAllReportApiRoutes();
-    if (routesResult.success) {
-      try {
-        const parsedRoutes: ApiRoute[] = JSON.parse(routesResult.output);
-        setRoutes(parsedRoutes);
-      } catch (e) {
-        console.error('Failed to parse report API routes response:', e);
-      }
+    try {
+      setRoutes(await tockCommands.getAllReportApiRoutes());
+    } catch (e) {
+      console.error('Failed to load report API routes:', e);
     }
     
     // Load report settings
-    const settingsResult = await tockCommands.getReportSettings();
-    if (settingsResult.success) {
-      try {
-        const parsedSettings: ReportSettings = JSON.parse(settingsResult.output);
-        setSettings(parsedSettings);
-      } catch (e) {
-        console.error('Failed to parse report settings response:', e);
-      }
+    try {
+      setSettings(await tockCommands.getReportSettings());
+    } catch (e) {
+      console.error('Failed to load report settings:', e);
+    }
+    
+    // Load reports still waiting for delivery
+    try {
+      setOutbox(await tockCommands.getOutbox());
+    } catch (e) {
+      console.error('Failed to load outbox:', e);
+    }
+    
+    // Load recent delivery attempts
+    try {
+      setDeliveries(await tockCommands.getReportDeliveryHistory(50));
+    } catch (e) {
+      console.error('Failed to load delivery history:', e);
     }
     
     setLoading(false);
@@ -58,106 +87,142 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
       return;
     }
 
-    const result = await tockCommands.addReportApiRoute(newRoute.name, newRoute.url);
-    if (result.success) {
+    try {
+      await tockCommands.addReportApiRoute(newRoute.name, newRoute.url, newRoute.auth);
       showMessage('success', 'Report API route added successfully');
-      setNewRoute({ name: '', url: '' });
+      setNewRoute({ name: '', url: '', auth: { type: 'none' } });
       setIsAdding(false);
       loadData();
-    } else {
-      showMessage('error', result.error || 'Failed to add route');
+    } catch (e) {
+      showMessage('error', `Failed to add route: ${errorMessage(e)}`);
     }
   };
 
   const handleUpdateRoute = async (id: number) => {
-    const result = await tockCommands.updateReportApiRoute(
-      id,
-      editRoute.name,
-      editRoute.url,
-      editRoute.enabled
-    );
-    if (result.success) {
+    try {
+      await tockCommands.updateReportApiRoute(
+        id,
+        editRoute.name,
+        editRoute.url,
+        editRoute.enabled,
+        editRoute.auth
+      );
       showMessage('success', 'Report API route updated successfully');
       setEditingId(null);
       loadData();
-    } else {
-      showMessage('error', result.error || 'Failed to update route');
+    } catch (e) {
+      showMessage('error', `Failed to update route: ${errorMessage(e)}`);
     }
   };
 
   const handleDeleteRoute = async (id: number) => {
     if (!confirm('Are you sure you want to delete this report API route?')) return;
 
-    const result = await tockCommands.deleteReportApiRoute(id);
-    if (result.success) {
+    try {
+      await tockCommands.deleteReportApiRoute(id);
       showMessage('success', 'Report API route deleted successfully');
       loadData();
-    } else {
-      showMessage('error', result.error || 'Failed to delete route');
+    } catch (e) {
+      showMessage('error', `Failed to delete route: ${errorMessage(e)}`);
     }
   };
 
   const handleToggleEnabled = async (route: ApiRoute) => {
-    const result = await tockCommands.updateReportApiRoute(
-      route.id!,
-      route.name,
-      route.url,
-      !route.enabled
-    );
-    if (result.success) {
+    try {
+      await tockCommands.updateReportApiRoute(
+        route.id!,
+        route.name,
+        route.url,
+        !route.enabled,
+        null
+      );
       loadData();
+    } catch (e) {
+      showMessage('error', `Failed to update route: ${errorMessage(e)}`);
     }
   };
 
   const startEdit = (route: ApiRoute) => {
     setEditingId(route.id!);
-    setEditRoute({ name: route.name, url: route.url, enabled: route.enabled });
+    setEditRoute({
+      name: route.name,
+      url: route.url,
+      enabled: route.enabled,
+      auth: route.auth ?? { type: 'none' },
+    });
   };
 
   const handleAutoSendToggle = async (enabled: boolean) => {
-    const result = await tockCommands.updateReportSettings(
-      enabled,
-      settings.selected_api_route_id
-    );
-    
-    if (result.success) {
+    try {
+      await tockCommands.updateReportSettings(
+        enabled,
+        settings.selected_api_route_id
+      );
       setSettings({ ...settings, auto_send_enabled: enabled });
       showMessage('success', enabled ? 'Auto-send enabled' : 'Auto-send disabled');
-    } else {
-      showMessage('error', result.error || 'Failed to update settings');
+    } catch (e) {
+      showMessage('error', `Failed to update settings: ${errorMessage(e)}`);
     }
   };
 
-  const handleApiRouteChange = async (routeId: number | undefined) => {
-    const result = await tockCommands.updateReportSettings(
-      settings.auto_send_enabled,
-      routeId
-    );
-    
-    if (result.success) {
+  const handleApiRouteChange = async (routeId: number | null) => {
+    try {
+      await tockCommands.updateReportSettings(
+        settings.auto_send_enabled,
+        routeId
+      );
       setSettings({ ...settings, selected_api_route_id: routeId });
       showMessage('success', 'API route updated');
-    } else {
-      showMessage('error', result.error || 'Failed to update settings');
+    } catch (e) {
+      showMessage('error', `Failed to update settings: ${errorMessage(e)}`);
+    }
+  };
+
+  const handlePeriodChange = async (periodType: string, cutoffDay: number | null, sendPartial: boolean) => {
+    try {
+      await tockCommands.updateReportPeriodSettings(periodType, cutoffDay, sendPartial);
+      setSettings({ ...settings, period_type: periodType, cutoff_day: cutoffDay, send_partial: sendPartial });
+    } catch (e) {
+      showMessage('error', `Failed to update report period: ${errorMessage(e)}`);
     }
   };
 
-  const handleSendReport = async () => {
+  const handleSendReport = async (closed: boolean) => {
     if (!settings.selected_api_route_id) {
       showMessage('error', 'Please select an API route first');
       return;
     }
     
     setSending(true);
-    const result = await tockCommands.sendMonthlyReportToApi(settings.selected_api_route_id);
-    setSending(false);
-    
-    if (result.success) {
-      showMessage('success', result.output);
-      // Reload settings to get updated last_sent_at
+    try {
+      showMessage('success', await tockCommands.sendMonthlyReportToApi(settings.selected_api_route_id, closed));
+    } catch (e) {
+      showMessage('error', `Failed to send report: ${errorMessage(e)}`);
+    } finally {
+      setSending(false);
+    }
+    // Reload to pick up last_sent_at, or the queued report after a failure
+    loadData();
+  };
+
+  const handleRetryOutbox = async (id: number) => {
+    try {
+      showMessage('success', await tockCommands.retryOutboxEntry(id));
+    } catch (e) {
+      showMessage('error', `Failed to send queued report: ${errorMessage(e)}`);
+    }
+    loadData();
+  };
+
+  const handleDiscardOutbox = async (id: number) => {
+    if (!confirm('Discard this queued report? It will not be sent.')) return;
+
+    try {
+      await tockCommands.discardOutboxEntry(id);
+      showMessage('success', 'Queued report discarded');
       loadData();
-    } else {
-      showMessage('error', result.error || 'Failed to send report');
+    } catch (e) {
+      showMessage('error', `Failed to discard queued report: ${errorMessage(e)}`);
     }
   };
 
@@ -177,7 +242,7 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
               to a selected API endpoint. The report includes all tracked activities for the current month.
             </p>
             <p className="text-xs text-blue-700 mt-2">
-              The API will receive a POST request with JSON containing: year, month, report (text), and generated_at (timestamp).
+              The API will receive a POST request with JSON containing: year, month, period (type, key, start, end), partial, report (text), and generated_at (timestamp).
             </p>
           </div>
         </div>
@@ -199,12 +264,12 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
             ) : (
               <select
                 value={settings.selected_api_route_id || ''}
-                onChange={(e) => handleApiRouteChange(e.target.value ? Number(e.target.value) : undefined)}
+                onChange={(e) => handleApiRouteChange(e.target.value ? Number(e.target.value) : null)}
                 className="w-full px-4 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500 focus:border-transparent"
               >
                 <option value="">-- Select an API route --</option>
                 {enabledRoutes.map((route) => (
-                  <option key={route.id} value={route.id}>
+                  <option key={route.id} value={route.id ?? undefined}>
                     {route.name} ({route.url})
                   </option>
                 ))}
@@ -231,28 +296,94 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
                 Enable automatic monthly report sending
               </div>
               <p className="text-xs text-slate-500 mt-1">
-                When enabled, reports will be automatically sent to the selected API route monthly.
+                When enabled, reports will be automatically sent to the selected API route on the schedule below.
                 {!settings.selected_api_route_id && ' (Select an API route first)'}
               </p>
             </label>
           </div>
 
-          {/* Manual send button */}
-          <div>
-            <button
-              onClick={handleSendReport}
-              disabled={sending || !settings.selected_api_route_id}
-              className="w-full px-6 py-3 bg-slate-700 text-white rounded-lg hover:bg-slate-800 transition-colors disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2 font-medium"
-            >
-              {sending ? (
-                'Sending...'
-              ) : (
-                <>
-                  <Send size={20} />
-                  Send Monthly Report Now
-                </>
+          {/* Reporting period */}
+          <div className="p-4 bg-white rounded-lg border border-slate-200 space-y-3">
+            <label className="block text-sm font-medium text-slate-700">
+              Reporting Period
+            </label>
+            <div className="flex gap-2">
+              <select
+                value={settings.period_type}
+                onChange={(e) => {
+                  const periodType = e.target.value as ReportPeriodType;
+                  handlePeriodChange(
+                    periodType,
+                    periodType === 'cutoff' ? settings.cutoff_day ?? 25 : settings.cutoff_day,
+                    settings.send_partial
+                  );
+                }}
+                className="flex-1 px-4 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500"
+              >
+                <option value="month">Calendar month</option>
+                <option value="iso_week">ISO week (Monday to Sunday)</option>
+                <option value="cutoff">Monthly, ending on a cutoff day</option>
+              </select>
+              {settings.period_type === 'cutoff' && (
+                <input
+                  type="number"
+                  min={1}
+                  max={28}
+                  value={settings.cutoff_day ?? 25}
+                  onChange={(e) => handlePeriodChange('cutoff', Number(e.target.value), settings.send_partial)}
+                  className="w-20 px-3 py-2 border border-slate-300 rounded-lg"
+                  title="Last day of each period (1-28)"
+                />
               )}
-            </button>
+            </div>
+            <label className="flex items-center gap-2 text-sm text-slate-700">
+              <input
+                type="checkbox"
+                checked={settings.send_partial}
+                onChange={(e) => handlePeriodChange(settings.period_type, settings.cutoff_day, e.target.checked)}
+                className="w-4 h-4"
+              />
+              Also send partial updates for the period in progress
+            </label>
+            <p className="text-xs text-slate-500">
+              Each closed period is sent automatically exactly once.
+              {settings.last_closed_period && ` Last closed period sent: ${settings.last_closed_period}.`}
+            </p>
+          </div>
+
+          {/* Auto-send schedule */}
+          <ReportScheduleEditor
+            schedule={settings.schedule ?? { type: 'period_end' }}
+            showMessage={showMessage}
+            onSaved={loadData}
+          />
+
+          {/* Manual send buttons */}
+          <div>
+            <div className="flex gap-2">
+              <button
+                onClick={() => handleSendReport(false)}
+                disabled={sending || !settings.selected_api_route_id}
+                className="flex-1 px-6 py-3 bg-slate-700 text-white rounded-lg hover:bg-slate-800 transition-colors disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2 font-medium"
+              >
+                {sending ? (
+                  'Sending...'
+                ) : (
+                  <>
+                    <Send size={20} />
+                    Send Current Period Now
+                  </>
+                )}
+              </button>
+              <button
+                onClick={() => handleSendReport(true)}
+                disabled={sending || !settings.selected_api_route_id}
+                className="flex-1 px-6 py-3 bg-slate-200 text-slate-800 rounded-lg hover:bg-slate-300 transition-colors disabled:opacity-50 disabled:cursor-not-allowed flex items-center justify-center gap-2 font-medium"
+              >
+                <Send size={20} />
+                Send Last Closed Period
+              </button>
+            </div>
             {selectedRoute && (
               <p className="text-xs text-slate-500 mt-2 text-center">
                 Report will be sent to: {selectedRoute.name} ({selectedRoute.url})
@@ -265,6 +396,97 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
             )}
           </div>
 
+          {/* Queued deliveries */}
+          {outbox.length > 0 && (
+            <div className="bg-yellow-50 border border-yellow-200 rounded-lg p-4">
+              <h3 className="text-sm font-semibold text-yellow-800 mb-2">
+                Queued Reports ({outbox.length})
+              </h3>
+              <div className="space-y-2">
+                {outbox.map((entry) => {
+                  const route = routes.find(r => r.id === entry.api_route_id);
+                  return (
+                    <div key={entry.id} className="bg-white p-3 rounded-lg border border-yellow-200 flex items-center gap-3">
+                      <div className="flex-1 min-w-0">
+                        <p className="text-sm font-medium text-slate-800">
+                          {entry.period}{entry.partial && ' (partial)'} → {route ? route.name : `route #${entry.api_route_id}`}
+                        </p>
+                        <p className="text-xs text-slate-600">
+                          {entry.attempts} failed attempt{entry.attempts === 1 ? '' : 's'}
+                          {entry.next_attempt_at
+                            ? ` · next retry ${new Date(entry.next_attempt_at).toLocaleString()}`
+                            : ' · automatic retries stopped'}
+                        </p>
+                        {entry.last_error && (
+                          <p className="text-xs text-red-600 truncate">{entry.last_error}</p>
+                        )}
+                      </div>
+                      <button
+                        onClick={() => handleRetryOutbox(entry.id!)}
+                        className="p-2 text-slate-600 hover:bg-slate-100 rounded-lg transition-colors"
+                        title="Retry now"
+                      >
+                        <RotateCw size={16} />
+                      </button>
+                      <button
+                        onClick={() => handleDiscardOutbox(entry.id!)}
+                        className="p-2 text-red-600 hover:bg-red-50 rounded-lg transition-colors"
+                        title="Discard"
+                      >
+                        <Trash2 size={16} />
+                      </button>
+                    </div>
+                  );
+                })}
+              </div>
+            </div>
+          )}
+
+          {/* Delivery history */}
+          {deliveries.length > 0 && (
+            <div className="bg-white rounded-lg border border-slate-200 p-4">
+              <button
+                onClick={() => setShowHistory(!showHistory)}
+                className="text-sm font-semibold text-slate-700 hover:text-slate-900"
+              >
+                {showHistory ? 'Hide' : 'Show'} Delivery History ({deliveries.length})
+              </button>
+              {showHistory && (
+                <div className="mt-3 overflow-x-auto">
+                  <table className="w-full text-xs text-left">
+                    <thead>
+                      <tr className="text-slate-500 border-b border-slate-200">
+                        <th className="py-1 pr-2">Sent</th>
+                        <th className="py-1 pr-2">Route</th>
+                        <th className="py-1 pr-2">Period</th>
+                        <th className="py-1 pr-2">Status</th>
+                        <th className="py-1 pr-2">Payload SHA-256</th>
+                      </tr>
+                    </thead>
+                    <tbody>
+                      {deliveries.map((delivery) => (
+                        <tr key={delivery.id} className="border-b border-slate-100 align-top">
+                          <td className="py-1 pr-2 whitespace-nowrap">
+                            {new Date(delivery.attempted_at).toLocaleString()}
+                          </td>
+                          <td className="py-1 pr-2" title={delivery.route_url}>{delivery.route_name}</td>
+                          <td className="py-1 pr-2">{delivery.period}{delivery.partial && ' (partial)'}</td>
+                          <td className={`py-1 pr-2 ${delivery.success ? 'text-green-700' : 'text-red-600'}`}>
+                            {delivery.status_code ?? '—'}
+                            {delivery.error && <div>{delivery.error}</div>}
+                          </td>
+                          <td className="py-1 pr-2 font-mono" title={delivery.payload_hash}>
+                            {delivery.payload_hash.slice(0, 12)}…
+                          </td>
+                        </tr>
+                      ))}
+                    </tbody>
+                  </table>
+                </div>
+              )}
+            </div>
+          )}
+
           {/* Report API Routes Management */}
           <div className="border-t border-slate-200 pt-4 mt-6">
             <div className="flex items-center justify-between mb-3">
@@ -297,6 +519,10 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
                     onChange={(e) => setNewRoute({ ...newRoute, url: e.target.value })}
                     className="w-full px-3 py-2 border border-slate-300 rounded-lg text-sm"
                   />
+                  <ApiAuthFields
+                    auth={newRoute.auth}
+                    onChange={(auth) => setNewRoute({ ...newRoute, auth })}
+                  />
                   <div className="flex gap-2">
                     <button
                       onClick={handleAddRoute}
@@ -308,7 +534,7 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
                     <button
                       onClick={() => {
                         setIsAdding(false);
-                        setNewRoute({ name: '', url: '' });
+                        setNewRoute({ name: '', url: '', auth: { type: 'none' } });
                       }}
                       className="flex-1 px-3 py-2 bg-slate-300 text-slate-700 rounded-lg hover:bg-slate-400 transition-colors text-sm flex items-center justify-center gap-1"
                     >
@@ -346,6 +572,10 @@ export const ReportApiSender: React.FC<ReportApiSenderProps> = ({ showMessage })
                           onChange={(e) => setEditRoute({ ...editRoute, url: e.target.value })}
                           className="w-full px-3 py-2 border border-slate-300 rounded-lg text-sm"
                         />
+                        <ApiAuthFields
+                          auth={editRoute.auth}
+                          onChange={(auth) => setEditRoute({ ...editRoute, auth })}
+                        />
                         <div className="flex items-center gap-3">
                           <label className="flex items-center gap-2 text-sm">
                             <input
@@ -0,0 +1,153 @@
+import React, { useState, useEffect } from 'react';
+import { errorMessage, tockCommands } from '../api';
+import { Clock } from 'lucide-react';
+import { NextReportDue, ReportSchedule } from '../types';
+
+interface ReportScheduleEditorProps {
+  schedule: ReportSchedule;
+  showMessage: (type: 'success' | 'error', text: string) => void;
+  onSaved: () => void;
+}
+
+const WEEKDAYS = ['monday', 'tuesday', 'wednesday', 'thursday', 'friday', 'saturday', 'sunday'];
+
+const inputClass = 'px-3 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500';
+
+// Default settings for a newly selected schedule type
+const defaultSchedule = (type: ReportSchedule['type']): ReportSchedule => {
+  switch (type) {
+    case 'interval':
+      return { type, days: 7 };
+    case 'weekly':
+      return { type, weekday: 'friday', time: '17:00' };
+    case 'monthly_day':
+      return { type, day: 1, time: '09:00' };
+    case 'last_working_day':
+      return { type, time: '17:00' };
+    case 'cron':
+      return { type, expression: '0 17 * * FRI' };
+    default:
+      return { type: 'period_end' };
+  }
+};
+
+export const ReportScheduleEditor: React.FC<ReportScheduleEditorProps> = ({ schedule, showMessage, onSaved }) => {
+  const [draft, setDraft] = useState<ReportSchedule>(schedule);
+  const [nextDue, setNextDue] = useState<NextReportDue | null>(null);
+
+  useEffect(() => {
+    setDraft(schedule);
+    loadNextDue();
+  }, [schedule]);
+
+  const loadNextDue = async () => {
+    try {
+      setNextDue(await tockCommands.getNextReportDue());
+    } catch (e) {
+      console.error('Failed to load next report due:', e);
+      setNextDue(null);
+    }
+  };
+
+  const handleSave = async () => {
+    try {
+      await tockCommands.updateReportSchedule(draft);
+      showMessage('success', 'Report schedule saved');
+      onSaved();
+    } catch (e) {
+      showMessage('error', `Failed to save report schedule: ${errorMessage(e)}`);
+    }
+  };
+
+  return (
+    <div className="p-4 bg-white rounded-lg border border-slate-200 space-y-3">
+      <label className="block text-sm font-medium text-slate-700">
+        Automatic Send Schedule
+      </label>
+      <div className="flex flex-wrap gap-2">
+        <select
+          value={draft.type}
+          onChange={(e) => setDraft(defaultSchedule(e.target.value as ReportSchedule['type']))}
+          className={`flex-1 ${inputClass}`}
+        >
+          <option value="period_end">As soon as a period closes</option>
+          <option value="interval">Every N days</option>
+          <option value="weekly">Every week</option>
+          <option value="monthly_day">Every month on a day</option>
+          <option value="last_working_day">Last working day of the month</option>
+          <option value="cron">Cron expression</option>
+        </select>
+
+        {draft.type === 'interval' && (
+          <input
+            type="number"
+            min={1}
+            value={draft.days}
+            onChange={(e) => setDraft({ ...draft, days: Number(e.target.value) })}
+            className={`w-24 ${inputClass}`}
+            title="Days between runs"
+          />
+        )}
+
+        {draft.type === 'weekly' && (
+          <select
+            value={draft.weekday}
+            onChange={(e) => setDraft({ ...draft, weekday: e.target.value })}
+            className={inputClass}
+          >
+            {WEEKDAYS.map((day) => (
+              <option key={day} value={day}>
+                {day.charAt(0).toUpperCase() + day.slice(1)}
+              </option>
+            ))}
+          </select>
+        )}
+
+        {draft.type === 'monthly_day' && (
+          <input
+            type="number"
+            min={1}
+            max={31}
+            value={draft.day}
+            onChange={(e) => setDraft({ ...draft, day: Number(e.target.value) })}
+            className={`w-20 ${inputClass}`}
+            title="Day of month"
+          />
+        )}
+
+        {(draft.type === 'weekly' || draft.type === 'monthly_day' || draft.type === 'last_working_day') && (
+          <input
+            type="time"
+            value={draft.time}
+            onChange={(e) => setDraft({ ...draft, time: e.target.value })}
+            className={inputClass}
+          />
+        )}
+
+        {draft.type === 'cron' && (
+          <input
+            type="text"
+            value={draft.expression}
+            onChange={(e) => setDraft({ ...draft, expression: e.target.value })}
+            className={`w-full font-mono ${inputClass}`}
+            placeholder="min hour day month weekday"
+          />
+        )}
+
+        <button
+          onClick={handleSave}
+          className="px-4 py-2 bg-slate-700 text-white rounded-lg hover:bg-slate-800 transition-colors"
+        >
+          Save
+        </button>
+      </div>
+      {nextDue && (
+        <p className="text-xs text-slate-500 flex items-center gap-1">
+          <Clock size={14} />
+          Next automatic run: {new Date(nextDue.next_due_at).toLocaleString()}
+          {!nextDue.auto_send_enabled && ' (auto-send is disabled)'}
+        </p>
+      )}
+    </div>
+  );
+};
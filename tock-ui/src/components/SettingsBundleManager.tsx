@@ -0,0 +1,113 @@
+import React, { useState } from 'react';
+import { open } from '@tauri-apps/plugin-dialog';
+import { Download, Upload } from 'lucide-react';
+import { errorMessage, tockCommands } from '../api';
+import { ImportReport, SettingsImportMode } from '../types';
+
+interface SettingsBundleManagerProps {
+  showMessage: (type: 'success' | 'error', text: string) => void;
+}
+
+export const SettingsBundleManager: React.FC<SettingsBundleManagerProps> = ({ showMessage }) => {
+  const [includeSecrets, setIncludeSecrets] = useState(false);
+  const [importMode, setImportMode] = useState<SettingsImportMode>('merge');
+  const [lastImport, setLastImport] = useState<ImportReport | null>(null);
+
+  const handleExport = async () => {
+    try {
+      const folder = await open({
+        directory: true,
+        multiple: false,
+        title: 'Select folder for the settings export',
+      });
+      if (!folder || typeof folder !== 'string') return;
+
+      showMessage('success', await tockCommands.exportSettingsBundle(folder, includeSecrets));
+    } catch (error) {
+      showMessage('error', `Failed to export settings: ${errorMessage(error)}`);
+    }
+  };
+
+  const handleImport = async () => {
+    try {
+      const file = await open({
+        multiple: false,
+        title: 'Select a tock-ui settings file',
+        filters: [{ name: 'Settings', extensions: ['json'] }],
+      });
+      if (!file || typeof file !== 'string') return;
+
+      if (importMode === 'replace' && !confirm('Replace all favorites, API routes and preferences with the imported settings?')) {
+        return;
+      }
+
+      const report = await tockCommands.importSettingsBundle(file, importMode);
+      setLastImport(report);
+      const added = report.favorites + report.api_routes + report.report_api_routes + report.cached_projects + report.preferences;
+      showMessage('success', `Imported ${added} items${report.conflicts.length ? ` (${report.conflicts.length} conflicts)` : ''}`);
+    } catch (error) {
+      showMessage('error', `Failed to import settings: ${errorMessage(error)}`);
+    }
+  };
+
+  return (
+    <div className="bg-slate-50 rounded-lg p-6 border border-slate-200 mt-6">
+      <h2 className="text-xl font-semibold text-slate-800 mb-4">Backup &amp; Restore Settings</h2>
+      <div className="space-y-4">
+        <div className="flex items-center justify-between gap-4">
+          <label className="flex items-center gap-2 text-sm text-slate-700">
+            <input
+              type="checkbox"
+              checked={includeSecrets}
+              onChange={(e) => setIncludeSecrets(e.target.checked)}
+            />
+            Include API credentials
+          </label>
+          <button
+            onClick={handleExport}
+            className="px-4 py-2 bg-slate-600 text-white rounded-lg hover:bg-slate-700 transition-colors flex items-center gap-2"
+          >
+            <Download size={18} />
+            Export
+          </button>
+        </div>
+
+        <div className="flex items-center justify-between gap-4">
+          <select
+            value={importMode}
+            onChange={(e) => setImportMode(e.target.value as SettingsImportMode)}
+            className="flex-1 px-3 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500"
+          >
+            <option value="merge">Merge with existing settings</option>
+            <option value="replace">Replace existing settings</option>
+          </select>
+          <button
+            onClick={handleImport}
+            className="px-4 py-2 bg-slate-600 text-white rounded-lg hover:bg-slate-700 transition-colors flex items-center gap-2"
+          >
+            <Upload size={18} />
+            Import
+          </button>
+        </div>
+
+        {lastImport && lastImport.conflicts.length > 0 && (
+          <div className="bg-amber-50 border border-amber-200 rounded-lg p-3">
+            <p className="text-sm font-medium text-amber-800 mb-2">Not imported as-is:</p>
+            <ul className="space-y-1">
+              {lastImport.conflicts.map((conflict, index) => (
+                <li key={index} className="text-xs text-amber-800">
+                  <span className="font-mono">{conflict.table}</span> {conflict.key}: {conflict.reason}
+                </li>
+              ))}
+            </ul>
+          </div>
+        )}
+
+        <p className="text-xs text-slate-500">
+          Exports favorites, API routes, report settings, cached projects and preferences as JSON.
+          Credentials are masked unless included and must be re-entered after importing.
+        </p>
+      </div>
+    </div>
+  );
+};
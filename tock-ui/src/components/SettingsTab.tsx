@@ -1,36 +1,52 @@
 import React, { useState, useEffect } from 'react';
-import { tockCommands } from '../api';
+import { errorMessage, tockCommands } from '../api';
 import { Download, Calendar, CalendarRange, FolderOpen } from 'lucide-react';
 import { open } from '@tauri-apps/plugin-dialog';
 import { ApiRouteManager } from './ApiRouteManager';
 import { ReportApiSender } from './ReportApiSender';
 import { UpdateChecker } from './UpdateChecker';
+import { BackgroundJobs } from './BackgroundJobs';
+import { SettingsBundleManager } from './SettingsBundleManager';
+import { DatabaseBackups } from './DatabaseBackups';
+import { PreferencesEditor } from './PreferencesEditor';
+import { ReportFormat, Settings } from '../types';
+
+const REPORT_FORMATS: { value: ReportFormat; label: string }[] = [
+  { value: 'txt', label: 'Text (.txt)' },
+  { value: 'csv', label: 'CSV (.csv)' },
+  { value: 'json', label: 'JSON (.json)' },
+  { value: 'markdown', label: 'Markdown (.md)' },
+  { value: 'html', label: 'HTML (.html)' },
+];
 
 interface SettingsTabProps {
   showMessage: (type: 'success' | 'error', text: string) => void;
+  onSettingsSaved: (settings: Settings) => void;
 }
 
-export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage }) => {
+export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage, onSettingsSaved }) => {
   const [reportType, setReportType] = useState<'today' | 'yesterday' | 'date' | 'range'>('today');
   const [selectedDate, setSelectedDate] = useState('');
   const [startDate, setStartDate] = useState('');
   const [endDate, setEndDate] = useState('');
   const [loading, setLoading] = useState(false);
   const [selectedFolder, setSelectedFolder] = useState<string>('');
+  const [reportFormat, setReportFormat] = useState<ReportFormat>('txt');
+  const [settings, setSettings] = useState<Settings | null>(null);
 
-  // Load saved folder path on mount
+  // Load saved folder and format on mount
   useEffect(() => {
-    loadSavedFolderPath();
+    loadSettings();
   }, []);
 
-  const loadSavedFolderPath = async () => {
+  const loadSettings = async () => {
     try {
-      const result = await tockCommands.getUserPreference('report_folder_path');
-      if (result.success && result.output) {
-        setSelectedFolder(result.output);
-      }
+      const loaded: Settings = await tockCommands.getSettings();
+      setSettings(loaded);
+      setSelectedFolder(loaded.report_folder_path ?? '');
+      setReportFormat(loaded.default_report_format as ReportFormat);
     } catch (error) {
-      console.error('Failed to load saved folder path:', error);
+      console.error('Failed to load settings:', error);
     }
   };
 
@@ -45,45 +61,48 @@ export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage }) => {
       if (folder && typeof folder === 'string') {
         setSelectedFolder(folder);
         // Save the folder path for next time
-        await tockCommands.setUserPreference('report_folder_path', folder);
+        if (settings) {
+          try {
+            setSettings(await tockCommands.updateSettings({ ...settings, report_folder_path: folder }));
+          } catch (error) {
+            console.warn('Failed to remember report folder:', errorMessage(error));
+          }
+        }
         showMessage('success', `Folder selected: ${folder}`);
       }
     } catch (error) {
       console.error('Error selecting folder:', error);
-      showMessage('error', `Failed to select folder: ${error}`);
+      showMessage('error', `Failed to select folder: ${errorMessage(error)}`);
     }
   };
 
   const handleGenerateReport = async () => {
-    setLoading(true);
-
-    let result;
-    const customPath = selectedFolder || undefined;
+    const customPath = selectedFolder || null;
     
-    if (reportType === 'date') {
-      if (!selectedDate) {
-        showMessage('error', 'Please select a date');
-        setLoading(false);
-        return;
-      }
-      result = await tockCommands.saveReportToFile('date', selectedDate, undefined, undefined, customPath);
-    } else if (reportType === 'range') {
-      if (!startDate || !endDate) {
-        showMessage('error', 'Please select both start and end dates');
-        setLoading(false);
-        return;
-      }
-      result = await tockCommands.saveReportToFile('range', undefined, startDate, endDate, customPath);
-    } else {
-      result = await tockCommands.saveReportToFile(reportType, undefined, undefined, undefined, customPath);
+    if (reportType === 'date' && !selectedDate) {
+      showMessage('error', 'Please select a date');
+      return;
+    }
+    if (reportType === 'range' && (!startDate || !endDate)) {
+      showMessage('error', 'Please select both start and end dates');
+      return;
     }
 
-    setLoading(false);
-
-    if (result.success) {
-      showMessage('success', result.output);
-    } else {
-      showMessage('error', result.error || 'Failed to generate report');
+    setLoading(true);
+    try {
+      let output;
+      if (reportType === 'date') {
+        output = await tockCommands.saveReportToFile('date', selectedDate, null, null, customPath, reportFormat);
+      } else if (reportType === 'range') {
+        output = await tockCommands.saveReportToFile('range', null, startDate, endDate, customPath, reportFormat);
+      } else {
+        output = await tockCommands.saveReportToFile(reportType, null, null, null, customPath, reportFormat);
+      }
+      showMessage('success', output);
+    } catch (error) {
+      showMessage('error', `Failed to generate report: ${errorMessage(error)}`);
+    } finally {
+      setLoading(false);
     }
   };
 
@@ -192,6 +211,22 @@ export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage }) => {
             </div>
           )}
 
+          {/* Format Selection */}
+          <div>
+            <label className="block text-sm font-medium text-slate-700 mb-2">
+              File Format
+            </label>
+            <select
+              value={reportFormat}
+              onChange={(e) => setReportFormat(e.target.value as ReportFormat)}
+              className="w-full px-4 py-2 border border-slate-300 rounded-lg focus:outline-none focus:ring-2 focus:ring-slate-500 focus:border-transparent"
+            >
+              {REPORT_FORMATS.map((f) => (
+                <option key={f.value} value={f.value}>{f.label}</option>
+              ))}
+            </select>
+          </div>
+
           {/* Folder Selection */}
           <div>
             <label className="block text-sm font-medium text-slate-700 mb-2">
@@ -233,13 +268,25 @@ export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage }) => {
           {/* Info Text */}
           <div className="bg-blue-50 border border-blue-200 rounded-lg p-4">
             <p className="text-sm text-blue-800">
-              <strong>Note:</strong> Reports will be saved as .txt files in your home directory.
-              The filename will include the date or period (e.g., "2024-01-01_tock.txt" or "2024-01-01_2024-01-31_tock.txt").
+              <strong>Note:</strong> Reports will be saved in your home directory unless another folder is selected.
+              The filename will include the date or period and the format's extension (e.g., "2024-01-01_tock.txt" or "2024-01-01_2024-01-31_tock.csv").
             </p>
           </div>
         </div>
       </div>
 
+      {/* Typed user preferences */}
+      {settings && (
+        <PreferencesEditor
+          settings={settings}
+          showMessage={showMessage}
+          onSaved={(saved) => {
+            setSettings(saved);
+            onSettingsSaved(saved);
+          }}
+        />
+      )}
+
       {/* Application Updates */}
       <UpdateChecker showMessage={showMessage} />
 
@@ -248,6 +295,15 @@ export const SettingsTab: React.FC<SettingsTabProps> = ({ showMessage }) => {
 
       {/* API Route Manager */}
       <ApiRouteManager showMessage={showMessage} />
+
+      {/* Settings export and import */}
+      <SettingsBundleManager showMessage={showMessage} />
+
+      {/* Database snapshots */}
+      <DatabaseBackups showMessage={showMessage} />
+
+      {/* Background scheduler status */}
+      <BackgroundJobs />
     </div>
   );
 };